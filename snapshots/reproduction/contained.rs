@@ -0,0 +1,27 @@
+#[test]
+fn test_cut_reproduction_2e385363() {
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+    let triangle = StaticTriangle2d::new(
+        StaticPoint2d::from((0.0, 0.0)),
+        StaticPoint2d::from((1.0, 0.0)),
+        StaticPoint2d::from((0.0, 1.0)),
+    );
+    let cutter = StaticTriangle2d::new(
+        StaticPoint2d::from((-10.0, -10.0)),
+        StaticPoint2d::from((30.0, -10.0)),
+        StaticPoint2d::from((-10.0, 30.0)),
+    );
+    let [inside, outside] = triangle.cut_to_triangles(&cutter).map(|side| {
+        side.iter().map(|t| t.coordinates_triangle()).collect::<Vec<_>>()
+    });
+    let assert_area = |triangles: &[StaticTriangle2d<StaticPoint2d>], expected: f64| {
+        let area: f64 = triangles.iter().map(|t| t.area().0.abs()).sum();
+        let tolerance = 1e-9 * expected.abs().max(1.0);
+        assert!((area - expected).abs() <= tolerance, "area {area}, expected {expected}");
+    };
+    assert_eq!(inside.len(), 1);
+    assert_eq!(outside.len(), 0);
+    assert_area(&inside, 0.5);
+    assert_area(&outside, 0.0);
+}
@@ -0,0 +1,8 @@
+//! Web worker computing the cuts of the cut page, loaded by the main application.
+use gloo::worker::Registrable;
+use triangle_play::scene::compute::CutWorker;
+
+fn main() {
+    wasm_logger::init(wasm_logger::Config::default());
+    CutWorker::registrar().register();
+}
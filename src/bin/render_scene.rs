@@ -0,0 +1,127 @@
+//! Renders a cut scene to SVG and PNG without a browser.
+//!
+//! ```sh
+//! cargo run --features cli --bin render-scene -- scene.json --png out.png --layers input,inside
+//! ```
+use std::error::Error;
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+use resvg::usvg::{fontdb, TreeParsing, TreePostProc};
+use resvg::{tiny_skia, usvg};
+use triangle_play::geometry::presets::Preset;
+use triangle_play::render::svg::{render_svg, SvgOptions};
+use triangle_play::render::theme::Theme;
+use triangle_play::scene::file::SceneFile;
+use triangle_play::scene::{Scene, SceneLayer};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, ValueEnum)]
+enum Layer {
+    Axes,
+    Input,
+    Vertices,
+    Annotations,
+    Inside,
+    Outside,
+    Precision,
+    Winding,
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Renders a cut scene to SVG and PNG")]
+struct Args {
+    /// Scene file in JSON format
+    #[arg(required_unless_present = "preset")]
+    scene: Option<PathBuf>,
+    /// Render a built-in preset instead of a scene file
+    #[arg(long, conflicts_with = "scene")]
+    preset: Option<String>,
+    /// Write the SVG to this file, to stdout if neither --svg nor --png is given
+    #[arg(long)]
+    svg: Option<PathBuf>,
+    /// Write a PNG to this file
+    #[arg(long)]
+    png: Option<PathBuf>,
+    #[arg(long, default_value_t = 800)]
+    width: u32,
+    #[arg(long, default_value_t = 600)]
+    height: u32,
+    /// Layers to draw
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [Layer::Axes, Layer::Vertices, Layer::Inside, Layer::Outside])]
+    layers: Vec<Layer>,
+    /// Light axes and labels on a dark background
+    #[arg(long)]
+    dark: bool,
+    /// Colors which stay apart for the common kinds of color blindness
+    #[arg(long)]
+    colorblind: bool,
+    /// Lines through the axis ticks
+    #[arg(long)]
+    grid: bool,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let scene = match (&args.scene, &args.preset) {
+        (_, Some(name)) => {
+            Scene::from_preset(Preset::find(name).ok_or_else(|| format!("unknown preset {name}"))?)
+        }
+        (Some(path), None) => Scene::from(&SceneFile::from_json(&std::fs::read_to_string(path)?)?),
+        (None, None) => unreachable!("enforced by clap"),
+    };
+
+    let result = scene.cut();
+    let mut figures = Vec::new();
+    for layer in &args.layers {
+        let layer = match layer {
+            Layer::Axes => continue,
+            Layer::Input => SceneLayer::Input,
+            Layer::Vertices => SceneLayer::Vertices,
+            Layer::Annotations => SceneLayer::Annotations,
+            Layer::Inside => SceneLayer::Inside,
+            Layer::Outside => SceneLayer::Outside,
+            Layer::Precision => SceneLayer::Precision,
+            Layer::Winding => SceneLayer::Winding,
+        };
+        figures.extend(scene.layer_figures(layer, &result));
+    }
+    let svg = render_svg(
+        &figures,
+        &SvgOptions {
+            width: args.width as f64,
+            height: args.height as f64,
+            axes: args.layers.contains(&Layer::Axes),
+            theme: Theme::select(args.dark, args.colorblind, args.grid),
+            ..SvgOptions::default()
+        },
+    );
+
+    if let Some(path) = &args.svg {
+        std::fs::write(path, &svg)?;
+    }
+    if let Some(path) = &args.png {
+        write_png(&svg, path)?;
+    }
+    if args.svg.is_none() && args.png.is_none() {
+        print!("{svg}");
+    }
+    Ok(())
+}
+
+fn write_png(svg: &str, path: &PathBuf) -> Result<(), Box<dyn Error>> {
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_system_fonts();
+    let mut tree = usvg::Tree::from_str(svg, &usvg::Options::default())?;
+    tree.postprocess(
+        usvg::PostProcessingSteps {
+            convert_text_into_paths: true,
+        },
+        &fontdb,
+    );
+    let size = tree.size.to_int_size();
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("invalid image size")?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.save_png(path)?;
+    Ok(())
+}
@@ -1,13 +1,52 @@
+use std::cell::RefCell;
 use std::ops::Deref;
+use std::rc::{Rc, Weak};
 
+use gloo::render::{request_animation_frame, AnimationFrame};
 use gloo::{events::EventListener, utils::window};
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlCanvasElement, MouseEvent};
 use yew::{
-    function_component, html, html::ChildrenRenderer, use_effect, use_state, Callback, Children,
-    Html, NodeRef, Properties,
+    function_component, html, html::ChildrenRenderer, use_effect, use_mut_ref, use_state, Callback,
+    Children, Html, NodeRef, Properties,
 };
 
+/// Redraws of a canvas, throttled to one per animation frame
+struct Frames<T> {
+    /// Latest render requested since the last frame, with the canvas size it is for
+    pending: Option<(T, (i32, i32))>,
+    /// Render drawn last, nothing is drawn while it stays the same
+    drawn: Option<(T, (i32, i32))>,
+    /// Handle of the latest requested frame, the request is cancelled when it is dropped
+    frame: Option<AnimationFrame>,
+    requested: bool,
+}
+
+impl<T> Default for Frames<T> {
+    fn default() -> Self {
+        Self {
+            pending: None,
+            drawn: None,
+            frame: None,
+            requested: false,
+        }
+    }
+}
+
+fn draw_frame<T: WithRender>(frames: &Weak<RefCell<Frames<T>>>, canvas: &HtmlCanvasElement) {
+    let Some(frames) = frames.upgrade() else {
+        return;
+    };
+    let mut frames = frames.borrow_mut();
+    // the handle of this frame stays in place until the next request replaces it, as
+    // dropping it here would free the running callback
+    frames.requested = false;
+    if let Some((render, size)) = frames.pending.take() {
+        render.clone().rand(canvas);
+        frames.drawn = Some((render, size));
+    }
+}
+
 /// A Canvas component is encapsulated.
 ///
 /// # Parameters and types
@@ -59,13 +98,15 @@ where
     let display_size = use_state(|| (10, 10));
 
     let size_listen_event_state = use_state(|| EventListener::new(&window(), "resize", |_| ()));
+    let frames = use_mut_ref(Frames::<T>::default);
 
     {
         let node_ref = node_ref.clone();
         let display_size = display_size.clone();
-        let render = props.render.clone();
+        let render = (*props.render).clone();
 
         use_effect(move || {
+            let size = *display_size;
             if let Some(canvas) = node_ref.cast::<HtmlCanvasElement>() {
                 if *is_first_render {
                     is_first_render.set(false);
@@ -83,7 +124,20 @@ where
                     ));
                 }
 
-                render.rand(&canvas);
+                // mouse moves re-render far more often than the screen refreshes
+                let mut state = frames.borrow_mut();
+                if state.drawn.as_ref() != Some(&(render.clone(), size)) {
+                    state.pending = Some((render, size));
+                    if !state.requested {
+                        state.requested = true;
+                        let frames = Rc::downgrade(&frames);
+                        state.frame = Some(request_animation_frame(move |_| {
+                            draw_frame(&frames, &canvas)
+                        }));
+                    }
+                } else {
+                    state.pending = None;
+                }
             }
             || ()
         });
@@ -0,0 +1,425 @@
+//! Runs the computations of a page in the [`CutWorker`], see [`triangle_play::scene::compute`].
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use gloo::timers::callback::Timeout;
+use gloo::worker::{Spawnable, WorkerBridge};
+use triangle_play::geometry::cut::CutResult;
+use triangle_play::geometry::guard::CutFailure;
+use triangle_play::geometry::robustness::Violations;
+use triangle_play::scene::compute::{
+    Answer, Completed, CutRequest, CutResponse, CutWorker, Job, LatestOnly,
+};
+use triangle_play::scene::file::SceneFile;
+use triangle_play::scene::sweep::Sweep;
+use triangle_play::scene::Scene;
+use yew::{hook, use_effect_with_deps, use_mut_ref, use_reducer, Reducible, UseReducerDispatcher};
+
+/// Script of the `cut-worker` binary, as placed by trunk
+const WORKER_PATH: &str = "/cut-worker.js";
+/// A request without answer after this time is given up on and the worker replaced. A
+/// panic aborts the worker in the web build, so it never answers.
+pub const WORKER_TIMEOUT_MS: u32 = 5000;
+
+/// Latest answer of the worker
+#[derive(Clone, PartialEq, Debug)]
+pub struct WorkerAnswer<I, O> {
+    /// Input of the answer, lags behind the submitted one while the worker is busy
+    pub input: Option<I>,
+    /// The answer, or the message why there is none
+    pub output: Option<Result<O, String>>,
+    pub busy: bool,
+    /// Counts the answers, comparing it is cheaper than comparing large outputs
+    pub generation: u64,
+}
+
+impl<I, O> Default for WorkerAnswer<I, O> {
+    fn default() -> Self {
+        Self {
+            input: None,
+            output: None,
+            busy: false,
+            generation: 0,
+        }
+    }
+}
+
+pub enum WorkerEvent<I, O> {
+    Submitted,
+    /// Replaces the whole state
+    Answered(Box<WorkerAnswer<I, O>>),
+}
+
+impl<I: Clone, O: Clone> Reducible for WorkerAnswer<I, O> {
+    type Action = WorkerEvent<I, O>;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            WorkerEvent::Submitted => WorkerAnswer {
+                busy: true,
+                ..(*self).clone()
+            },
+            WorkerEvent::Answered(state) => WorkerAnswer {
+                generation: self.generation + 1,
+                ..*state
+            },
+        }
+        .into()
+    }
+}
+
+/// Latest cut answered by the worker
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WorkerCut {
+    /// Scene of the result, lags behind the edited scene while the worker is busy
+    pub scene: Option<Scene>,
+    pub result: Option<Result<CutResult, CutFailure>>,
+    pub busy: bool,
+}
+
+struct WorkerClient<I: Clone + 'static, O: Clone + 'static> {
+    bridge: WorkerBridge<CutWorker>,
+    queue: LatestOnly<I>,
+    watchdog: Option<Timeout>,
+    dispatch: UseReducerDispatcher<WorkerAnswer<I, O>>,
+    job: fn(&I) -> Job,
+    output: fn(Answer) -> Option<O>,
+    timeout_ms: u32,
+}
+
+type SharedClient<I, O> = RefCell<Option<WorkerClient<I, O>>>;
+
+/// Computes the [`Job`] of `input` in a web worker whenever it changes, nothing while
+/// it is `None`. `output` picks the result out of the answer. The worker is started on
+/// first use and stopped with the component, answers taking longer than `timeout_ms`
+/// are given up on.
+#[hook]
+pub fn use_worker<I, O>(
+    input: Option<I>,
+    job: fn(&I) -> Job,
+    output: fn(Answer) -> Option<O>,
+    timeout_ms: u32,
+) -> WorkerAnswer<I, O>
+where
+    I: Clone + PartialEq + 'static,
+    O: Clone + 'static,
+{
+    let state = use_reducer(WorkerAnswer::default);
+    let client = use_mut_ref(|| None::<WorkerClient<I, O>>);
+    let dispatch = state.dispatcher();
+    use_effect_with_deps(
+        move |input| {
+            let Some(input) = input else {
+                return;
+            };
+            {
+                let mut slot = client.borrow_mut();
+                let client_ref = slot.get_or_insert_with(|| WorkerClient {
+                    bridge: spawn(Rc::downgrade(&client)),
+                    queue: LatestOnly::default(),
+                    watchdog: None,
+                    dispatch: dispatch.clone(),
+                    job,
+                    output,
+                    timeout_ms,
+                });
+                if let Some(request) = client_ref.queue.submit(input.clone()) {
+                    client_ref.send(Rc::downgrade(&client), request);
+                }
+            }
+            dispatch.dispatch(WorkerEvent::Submitted);
+        },
+        input,
+    );
+    (*state).clone()
+}
+
+/// Cuts `scene` in a web worker
+#[hook]
+pub fn use_worker_cut(scene: Scene) -> WorkerCut {
+    let answer = use_worker(
+        Some(scene),
+        |scene| Job::Cut(SceneFile::from(scene)),
+        |answer| match answer {
+            Answer::Cut(result) => Some(result),
+            _ => None,
+        },
+        WORKER_TIMEOUT_MS,
+    );
+    WorkerCut {
+        scene: answer.input,
+        result: answer.input.zip(answer.output).map(|(scene, output)| {
+            output
+                .and_then(|result| result)
+                .map_err(|message| CutFailure {
+                    message,
+                    triangle: scene.triangle,
+                    cutter: scene.cutter,
+                })
+        }),
+        busy: answer.busy,
+    }
+}
+
+impl<I: Clone, O: Clone> WorkerClient<I, O> {
+    fn send(&mut self, shared: Weak<SharedClient<I, O>>, (id, input): (u64, I)) {
+        self.bridge.send(CutRequest {
+            id,
+            job: (self.job)(&input),
+        });
+        self.watchdog = Some(Timeout::new(self.timeout_ms, move || {
+            on_timeout(&shared, id)
+        }));
+    }
+}
+
+fn spawn<I: Clone + 'static, O: Clone + 'static>(
+    shared: Weak<SharedClient<I, O>>,
+) -> WorkerBridge<CutWorker> {
+    CutWorker::spawner()
+        .callback(move |response| on_response(&shared, response))
+        .spawn(WORKER_PATH)
+}
+
+fn on_response<I: Clone + 'static, O: Clone + 'static>(
+    shared: &Weak<SharedClient<I, O>>,
+    response: CutResponse,
+) {
+    let Some(shared_rc) = shared.upgrade() else {
+        return;
+    };
+    let mut slot = shared_rc.borrow_mut();
+    let Some(client) = slot.as_mut() else {
+        return;
+    };
+    let event = match client.queue.complete(response.id) {
+        Completed::Latest(input) => {
+            client.watchdog = None;
+            let output = (client.output)(response.answer)
+                .ok_or_else(|| "the worker answered another kind of job".to_string());
+            answered(input, output, false)
+        }
+        // the answer is stale, the next request replaces the watchdog
+        Completed::Superseded(id, input) => {
+            client.send(shared.clone(), (id, input));
+            return;
+        }
+        Completed::Unknown => return,
+    };
+    let dispatch = client.dispatch.clone();
+    // dispatching may render the component right away, which must not find the client
+    // borrowed
+    drop(slot);
+    dispatch.dispatch(event);
+}
+
+fn on_timeout<I: Clone + 'static, O: Clone + 'static>(shared: &Weak<SharedClient<I, O>>, id: u64) {
+    let Some(shared_rc) = shared.upgrade() else {
+        return;
+    };
+    let mut slot = shared_rc.borrow_mut();
+    let Some(client) = slot.as_mut() else {
+        return;
+    };
+    // this runs inside the watchdog, dropping it would free the running closure
+    if let Some(watchdog) = client.watchdog.take() {
+        watchdog.forget();
+    }
+    let Some((_, lost)) = client
+        .queue
+        .running()
+        .cloned()
+        .filter(|(running, _)| *running == id)
+    else {
+        return;
+    };
+    client.bridge = spawn(shared.clone());
+    let busy = match client.queue.complete(id) {
+        Completed::Superseded(id, input) => {
+            client.send(shared.clone(), (id, input));
+            true
+        }
+        Completed::Latest(_) | Completed::Unknown => false,
+    };
+    let message = format!(
+        "no answer from the worker within {} s, the computation probably panicked",
+        client.timeout_ms / 1000
+    );
+    let event = answered(lost, Err(message), busy);
+    let dispatch = client.dispatch.clone();
+    drop(slot);
+    dispatch.dispatch(event);
+}
+
+fn answered<I, O>(input: I, output: Result<O, String>, busy: bool) -> WorkerEvent<I, O> {
+    WorkerEvent::Answered(Box::new(WorkerAnswer {
+        input: Some(input),
+        output: Some(output),
+        busy,
+        generation: 0,
+    }))
+}
+
+/// Sweep as far as the worker checked it
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WorkerSweep {
+    pub sweep: Option<Rc<Sweep>>,
+    pub busy: bool,
+    /// Counts the states, a change of the sweep changes it as well and is cheaper to
+    /// compare
+    pub generation: u64,
+}
+
+impl Reducible for WorkerSweep {
+    type Action = WorkerSweep;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        action.into()
+    }
+}
+
+struct SweepClient {
+    bridge: WorkerBridge<CutWorker>,
+    id: u64,
+    sweep: Sweep,
+    generation: u64,
+    watchdog: Option<Timeout>,
+    dispatch: UseReducerDispatcher<WorkerSweep>,
+}
+
+type SharedSweepClient = RefCell<Option<SweepClient>>;
+
+/// Checks the cells of `sweep` in a web worker, a new sweep abandons the running one.
+/// A cell the worker does not answer within [`WORKER_TIMEOUT_MS`] is recorded as
+/// panicked and the sweep goes on with the next cell in a new worker.
+#[hook]
+pub fn use_worker_sweep(sweep: Option<Sweep>) -> WorkerSweep {
+    let state = use_reducer(WorkerSweep::default);
+    let client = use_mut_ref(|| None::<SweepClient>);
+    let dispatch = state.dispatcher();
+    use_effect_with_deps(
+        move |sweep| {
+            let Some(sweep) = sweep else {
+                return;
+            };
+            let (id, generation) = client
+                .borrow()
+                .as_ref()
+                .map_or((1, 0), |running| (running.id + 1, running.generation));
+            let shared = Rc::downgrade(&client);
+            // replacing the client stops the worker of the running sweep
+            let mut next = SweepClient {
+                bridge: spawn_sweep(shared.clone()),
+                id,
+                sweep: sweep.clone(),
+                generation,
+                watchdog: None,
+                dispatch: dispatch.clone(),
+            };
+            next.send(shared, 0);
+            let state = next.state();
+            *client.borrow_mut() = Some(next);
+            dispatch.dispatch(state);
+        },
+        sweep,
+    );
+    (*state).clone()
+}
+
+impl SweepClient {
+    fn send(&mut self, shared: Weak<SharedSweepClient>, from: usize) {
+        self.bridge.send(CutRequest {
+            id: self.id,
+            job: Job::Sweep {
+                sweep: Sweep {
+                    cells: Vec::new(),
+                    ..self.sweep.clone()
+                },
+                from,
+            },
+        });
+        self.watch(shared);
+    }
+
+    fn watch(&mut self, shared: Weak<SharedSweepClient>) {
+        let id = self.id;
+        self.watchdog = Some(Timeout::new(WORKER_TIMEOUT_MS, move || {
+            on_sweep_timeout(&shared, id)
+        }));
+    }
+
+    fn state(&mut self) -> WorkerSweep {
+        self.generation += 1;
+        WorkerSweep {
+            sweep: Some(Rc::new(self.sweep.clone())),
+            busy: !self.sweep.is_complete(),
+            generation: self.generation,
+        }
+    }
+}
+
+fn spawn_sweep(shared: Weak<SharedSweepClient>) -> WorkerBridge<CutWorker> {
+    CutWorker::spawner()
+        .callback(move |response| on_sweep_response(&shared, response))
+        .spawn(WORKER_PATH)
+}
+
+fn on_sweep_response(shared: &Weak<SharedSweepClient>, response: CutResponse) {
+    let Some(shared_rc) = shared.upgrade() else {
+        return;
+    };
+    let mut slot = shared_rc.borrow_mut();
+    let Some(client) = slot.as_mut() else {
+        return;
+    };
+    let Answer::SweepCell { index, violations } = response.answer else {
+        return;
+    };
+    if response.id != client.id || index != client.sweep.cells.len() {
+        return;
+    }
+    client.sweep.cells.push(violations);
+    let complete = client.sweep.is_complete();
+    if complete {
+        client.watchdog = None;
+    } else {
+        client.watch(shared.clone());
+    }
+    // one render per row is enough to watch the heat map grow
+    if !complete && client.sweep.cells.len() % client.sweep.steps != 0 {
+        return;
+    }
+    let (dispatch, state) = (client.dispatch.clone(), client.state());
+    drop(slot);
+    dispatch.dispatch(state);
+}
+
+fn on_sweep_timeout(shared: &Weak<SharedSweepClient>, id: u64) {
+    let Some(shared_rc) = shared.upgrade() else {
+        return;
+    };
+    let mut slot = shared_rc.borrow_mut();
+    let Some(client) = slot.as_mut() else {
+        return;
+    };
+    if client.id != id {
+        return;
+    }
+    // this runs inside the watchdog, dropping it would free the running closure
+    if let Some(watchdog) = client.watchdog.take() {
+        watchdog.forget();
+    }
+    client.sweep.cells.push(Some(Violations {
+        panicked: true,
+        ..Violations::default()
+    }));
+    client.bridge = spawn_sweep(shared.clone());
+    if !client.sweep.is_complete() {
+        let from = client.sweep.cells.len();
+        client.send(shared.clone(), from);
+    }
+    let (dispatch, state) = (client.dispatch.clone(), client.state());
+    drop(slot);
+    dispatch.dispatch(state);
+}
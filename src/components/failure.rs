@@ -0,0 +1,146 @@
+//! On-screen reports of failed geometry computations.
+//!
+//! Failures caught by [`guarded_cut`](triangle_play::geometry::guard::guarded_cut) are
+//! shown right away. The web build aborts on panic, so the panic hook stores the report
+//! in the session storage and reloads the page, which shows it after the restart. The
+//! scene edited on the cut page is stored along with it and restored.
+
+use std::cell::Cell;
+
+use gloo::storage::{SessionStorage, Storage};
+use js_sys::{Function, Reflect};
+use patternfly_yew::prelude::{use_toaster, Action, AlertType, Toast};
+use serde::{Deserialize, Serialize};
+use triangle_play::geometry::guard::{running_cut, CutFailure};
+use triangle_play::scene::file::SceneFile;
+use triangle_play::scene::reproduction::{panic_test, scene_json};
+use triangle_play::scene::Scene;
+use wasm_bindgen::JsCast;
+use yew::{function_component, html, use_effect_with_deps, Callback, Html};
+
+const CRASH_REPORT_KEY: &str = "triangle-play.crash-report";
+const EDITED_SCENE_KEY: &str = "triangle-play.edited-scene";
+
+thread_local! {
+    static EDITED_SCENE: Cell<Option<Scene>> = const { Cell::new(None) };
+}
+
+/// Failure which ended the previous run of the application
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+struct CrashReport {
+    message: String,
+    /// Input of the cut running at the time of the panic
+    scene: Option<SceneFile>,
+}
+
+/// Logs panics and keeps them for the [`CrashReportToast`] of the reloaded page
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        log::error!("{info}");
+        let report = CrashReport {
+            message: info.to_string(),
+            scene: running_cut().map(|(triangle, cutter)| {
+                SceneFile::from(&Scene {
+                    triangle,
+                    cutter,
+                    preset: None,
+                })
+            }),
+        };
+        // a report which was not shown yet means the reloaded page panicked again
+        let reloaded = SessionStorage::get::<CrashReport>(CRASH_REPORT_KEY).is_ok();
+        if let Some(scene) = EDITED_SCENE.with(Cell::get).filter(|_| !reloaded) {
+            if let Err(err) = SessionStorage::set(EDITED_SCENE_KEY, SceneFile::from(&scene)) {
+                log::error!("storing the edited scene failed: {err}");
+            }
+        }
+        if !reloaded && SessionStorage::set(CRASH_REPORT_KEY, report).is_ok() {
+            if let Err(err) = gloo::utils::window().location().reload() {
+                log::error!("reload after panic failed: {err:?}");
+            }
+        }
+    }));
+}
+
+/// Keeps `scene` to restore it after a panic ended the application
+pub fn remember_edited_scene(scene: &Scene) {
+    EDITED_SCENE.with(|edited| edited.set(Some(*scene)));
+}
+
+/// Scene edited when a panic ended the previous run, only returned once
+pub fn take_restored_scene() -> Option<Scene> {
+    let file = SessionStorage::get::<SceneFile>(EDITED_SCENE_KEY).ok()?;
+    SessionStorage::delete(EDITED_SCENE_KEY);
+    Some(Scene::from(&file))
+}
+
+/// Shows the report of a panic which ended the previous run
+#[function_component(CrashReportToast)]
+pub fn crash_report_toast() -> Html {
+    let toaster = use_toaster();
+    use_effect_with_deps(
+        move |_| {
+            if let Ok(report) = SessionStorage::get::<CrashReport>(CRASH_REPORT_KEY) {
+                SessionStorage::delete(CRASH_REPORT_KEY);
+                let title = "The application restarted after a panic";
+                let toast = match report.scene.as_ref().map(Scene::from) {
+                    Some(scene) => failure_toast(
+                        title,
+                        &CutFailure {
+                            message: report.message,
+                            triangle: scene.triangle,
+                            cutter: scene.cutter,
+                        },
+                    ),
+                    None => Toast {
+                        title: title.to_string(),
+                        r#type: AlertType::Danger,
+                        body: html! { <p>{report.message}</p> },
+                        ..Default::default()
+                    },
+                };
+                if let Some(toaster) = toaster {
+                    toaster.toast(toast);
+                }
+            }
+        },
+        (),
+    );
+    html! {}
+}
+
+/// Toast with the message, the exact input coordinates and ways to copy a reproduction
+pub fn failure_toast(title: &str, failure: &CutFailure) -> Toast {
+    let scene = Scene::from(failure);
+    Toast {
+        title: title.to_string(),
+        r#type: AlertType::Danger,
+        body: html! {
+            <>
+                <p>{&failure.message}</p>
+                <p><code>{failure.coordinates_text()}</code></p>
+            </>
+        },
+        actions: vec![
+            Action::new("Copy scene JSON", copy_callback(scene_json(&scene))),
+            Action::new("Copy Rust test", copy_callback(panic_test(&scene))),
+        ],
+        ..Default::default()
+    }
+}
+
+fn copy_callback(text: String) -> Callback<()> {
+    Callback::from(move |_| copy_to_clipboard(&text))
+}
+
+/// Writes `text` to the clipboard, the clipboard API of `web_sys` is not stable yet
+pub fn copy_to_clipboard(text: &str) {
+    let navigator = gloo::utils::window().navigator();
+    let result = Reflect::get(&navigator, &"clipboard".into()).and_then(|clipboard| {
+        let write_text: Function = Reflect::get(&clipboard, &"writeText".into())?.dyn_into()?;
+        write_text.call1(&clipboard, &text.into())
+    });
+    if let Err(err) = result {
+        log::warn!("copy to clipboard failed: {err:?}");
+    }
+}
@@ -1,2 +1,6 @@
 pub mod canvas;
+pub mod compute;
+pub mod failure;
 pub mod render2d;
+pub mod split_view;
+pub mod theme;
@@ -6,104 +6,42 @@ use std::ops::Deref;
 use std::rc::Rc;
 
 use log::info;
-use num_traits::{One, Pow};
-use triangles::prelude::{
-    AnyPolygon, BoundingBox, BoundingBoxValues, Number, Point2d, Polygon2d, StaticPoint2d,
+use num_traits::One;
+use triangle_play::display::{arc_sweep, AnyGeometry, Figure, Layer, Layers, HEAT_MAP_OPACITY};
+use triangle_play::render::annotation::{arrow_head, dimension_layout, LABEL_OFFSET};
+use triangle_play::render::axes::{grid_steps, layout_axes, TextAlign};
+pub use triangle_play::render::projection::{
+    AspectMode, Margin, ProjectionConfig, ScreenProject2d, YAxis,
 };
+use triangle_play::render::theme::{Palette, Theme};
+use triangles::prelude::{Number, Polygon2d, StaticPoint2d};
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, MouseEvent};
 use yew::html::IntoPropValue;
-use yew::{function_component, html, Html};
+use yew::{function_component, html, use_mut_ref, Html};
 use yew::{Callback, Properties};
 
 use crate::components::canvas::Canvas;
 use crate::components::canvas::WithRender;
-use crate::components::render2d::tick_sequence::TickSequence;
-use crate::components::render2d::CssStyle::Color;
+use crate::components::theme::use_theme;
 
 //Befor impl WithRander, derive Clone and PartialEq first!
 #[derive(Clone)]
 struct Render {
-    display_list: Rc<[Figure]>,
+    layers: Layers,
+    projection: ProjectionConfig,
+    theme: Theme,
     last_projection: Rc<RefCell<Option<ScreenProject2d>>>,
 }
 
 impl PartialEq for Render {
     fn eq(&self, other: &Self) -> bool {
-        self.display_list == other.display_list
+        self.layers == other.layers
+            && self.projection == other.projection
+            && self.theme == other.theme
     }
 }
 
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-struct ScreenProject2d {
-    scale: Number,
-    x_offset: Number,
-    y_offset: Number,
-}
-
-impl ScreenProject2d {
-    fn from_bounding_box(bbox: &BoundingBoxValues, canvas_width: f64, canvas_height: f64) -> Self {
-        let canvas_width = Into::<Number>::into(canvas_width);
-        let canvas_height = Into::<Number>::into(canvas_height);
-        let fact = Number::min(canvas_width / bbox.width(), canvas_height / bbox.height());
-        let x_offset = -bbox.min_x() * fact + ((canvas_width - bbox.width() * fact) / 2.0);
-
-        let y_offset = -bbox.max_y() * -fact + ((canvas_height - bbox.height() * fact) / 2.0);
-        Self {
-            scale: fact,
-            x_offset,
-            y_offset,
-        }
-    }
-    fn project_point<P: Point2d>(&self, p: &P) -> (f64, f64) {
-        let StaticPoint2d { x, y } = p.coordinates();
-        (
-            (self.scale * x + self.x_offset).into(),
-            (-self.scale * y + self.y_offset).into(),
-        )
-    }
-    fn find_origin_point(&self, x: i32, y: i32) -> (Number, Number) {
-        (
-            ((Number::from(x as f64) - self.x_offset) / self.scale),
-            ((Number::from(y as f64) - self.y_offset) / -self.scale),
-        )
-    }
-
-    pub fn scale(&self) -> Number {
-        self.scale
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use triangles::prelude::BoundingBoxValues;
-
-    use crate::components::render2d::ScreenProject2d;
-
-    #[test]
-    fn test_projection() {
-        let b = BoundingBoxValues::new((-10.0).into(), (-30.0).into(), 100.0.into(), 150.0.into());
-        let p = ScreenProject2d::from_bounding_box(&b, 65.0, 90.0);
-        let (x, y) = p.project_point(&(42.0, 23.0).into());
-        assert_eq!(x, 31.0);
-        assert_eq!(y, 63.5);
-        let (x, y) = p.find_origin_point(x.round() as i32, y.round() as i32);
-        println!("{x},{y}");
-    }
-}
-
-#[derive(Debug)]
-enum TickSideHorizontal {
-    Left,
-    Right,
-}
-
-#[derive(Debug)]
-enum TickSideVertical {
-    Top,
-    Bottom,
-}
-
 impl WithRender for Render {
     fn rand(self, canvas: &HtmlCanvasElement) {
         let mut ctx: CanvasRenderingContext2d = canvas
@@ -113,312 +51,227 @@ impl WithRender for Render {
             .dyn_into()
             .unwrap();
 
-        ctx.set_stroke_style(&CssColor::Black.value());
-        ctx.set_fill_style(&CssColor::Black.value());
-
+        let theme = self.theme;
         let width = canvas.width() as f64;
         let height = canvas.height() as f64;
-        ctx.clear_rect(0.0, 0.0, width, height);
-
-        let mut bbox: BoundingBox = BoundingBox::default();
-
-        for figure in self.display_list.iter() {
-            bbox += figure.bbox();
-        }
-        match bbox {
-            BoundingBox::Empty => {}
-            BoundingBox::Box(bbox) => {
-                let bbox = bbox.expand(0.1.into());
-                let p = ScreenProject2d::from_bounding_box(&bbox, width, height);
+        ctx.set_fill_style(&JsValue::from_str(theme.background));
+        ctx.fill_rect(0.0, 0.0, width, height);
+        ctx.set_font(theme.label_font);
+
+        let axis = JsValue::from_str(theme.axis);
+        ctx.set_stroke_style(&axis);
+        ctx.set_fill_style(&axis);
+
+        let bbox = self.layers.fit_bbox();
+        match self.projection.fit(&bbox, width, height) {
+            None => {}
+            Some((p, bbox)) => {
                 self.last_projection.clone().borrow_mut().replace(Some(p));
-                let (zero_x, zero_y) = p.project_point::<StaticPoint2d>(&(0.0, 0.0).into());
-                let (min_x, min_y) =
-                    p.project_point::<StaticPoint2d>(&(bbox.min_x(), bbox.min_y()).into());
-                let (max_x, max_y) =
-                    p.project_point::<StaticPoint2d>(&(bbox.max_x(), bbox.max_y()).into());
-                let (tick_y, tick_side_vertical) = if zero_y < 0.0 {
-                    (0.0, TickSideVertical::Bottom)
-                } else if zero_y > height {
-                    (height, TickSideVertical::Top)
-                } else {
-                    ctx.begin_path();
-                    ctx.move_to(min_x, zero_y);
-                    ctx.line_to(max_x, zero_y);
-                    ctx.stroke();
-                    (
-                        zero_y,
-                        if zero_y > height / 2.0 {
-                            TickSideVertical::Top
-                        } else {
-                            TickSideVertical::Bottom
-                        },
-                    )
-                };
-
-                let (tick_x, tick_side_horizontal) = if zero_x < 0.0 {
-                    (min_x, TickSideHorizontal::Right)
-                } else if zero_x > width {
-                    (max_x, TickSideHorizontal::Left)
-                } else {
+                let axes = layout_axes(&p, &bbox, width, height);
+                if let Some(grid) = theme.grid {
+                    ctx.set_stroke_style(&JsValue::from_str(grid));
+                    stroke_lines(&ctx, axes.grid);
+                    ctx.set_stroke_style(&axis);
+                }
+                for ((x1, y1), (x2, y2)) in axes.lines {
                     ctx.begin_path();
-                    ctx.move_to(zero_x, min_y);
-                    ctx.line_to(zero_x, max_y);
+                    ctx.move_to(x1, y1);
+                    ctx.line_to(x2, y2);
                     ctx.stroke();
-                    (
-                        zero_x,
-                        if zero_x > width / 2.0 {
-                            TickSideHorizontal::Left
-                        } else {
-                            TickSideHorizontal::Right
-                        },
-                    )
-                };
-
-                let y_step = find_optimal_step(40.0 / p.scale().0);
-                for y_tick in TickSequence::new(bbox.min_y().0, bbox.max_y().0, y_step).iter() {
-                    Self::draw_y_tick(&ctx, &p, &tick_side_horizontal, y_tick, tick_x);
                 }
-
-                let x_step = find_optimal_step(40.0 / p.scale().0);
-                for x_tick in TickSequence::new(bbox.min_x().0, bbox.max_x().0, x_step).iter() {
-                    Self::draw_x_tick(&ctx, &p, &tick_side_vertical, x_tick, tick_y);
+                for label in axes.labels {
+                    ctx.set_text_align(match label.align {
+                        TextAlign::Left => "left",
+                        TextAlign::Center => "center",
+                        TextAlign::Right => "right",
+                    });
+                    ctx.fill_text(&label.text, label.x, label.y).unwrap();
                 }
+                ctx.set_text_align("start");
 
-                for figure in self.display_list.iter() {
-                    figure.draw(&mut ctx, &p);
+                for figure in self.layers.figures() {
+                    draw_figure(figure, &mut ctx, &p, &theme.palette);
                 }
             }
         }
     }
 }
 
-mod tick_sequence;
-
-impl Render {
-    fn draw_x_tick(
-        ctx: &CanvasRenderingContext2d,
-        p: &ScreenProject2d,
-        tick_side_vertical: &TickSideVertical,
-        x_tick: f64,
-        tick_y: f64,
-    ) {
-        let (x, _) = p.project_point::<StaticPoint2d>(&(x_tick, 0.0).into());
-        let label = &format!("{}", x_tick);
-        let text_metrics = ctx.measure_text(label).unwrap();
-        let text_width = text_metrics.width();
-        match tick_side_vertical {
-            TickSideVertical::Top => {
+fn draw_figure(
+    figure: &Figure,
+    ctx: &mut CanvasRenderingContext2d,
+    p: &ScreenProject2d,
+    palette: &Palette,
+) {
+    let style = JsValue::from_str(palette.css_value(figure.style()));
+    match figure.geometry() {
+        AnyGeometry::Polygon(polygon) => {
+            let mut iter = polygon.points();
+            if let Some(start_pt) = iter.next() {
+                let (x, y) = p.project_point(start_pt);
                 ctx.begin_path();
-                ctx.move_to(x, tick_y);
-                ctx.line_to(x, tick_y - 5.0);
-                ctx.stroke();
-                ctx.fill_text(label, x - text_width / 2.0, tick_y - 10.0)
-                    .unwrap();
-            }
-            TickSideVertical::Bottom => {
-                ctx.begin_path();
-                ctx.move_to(x, tick_y);
-                ctx.line_to(x, tick_y + 5.0);
+                ctx.set_stroke_style(&style);
+                ctx.move_to(x, y);
+                for next_pt in iter {
+                    let (x, y) = p.project_point(next_pt);
+                    ctx.line_to(x, y);
+                }
+                ctx.close_path();
                 ctx.stroke();
-                ctx.fill_text(label, x - text_width / 2.0, tick_y + 20.0)
-                    .unwrap();
             }
         }
-    }
-    fn draw_y_tick(
-        ctx: &CanvasRenderingContext2d,
-        p: &ScreenProject2d,
-        tick_side_horizontal: &TickSideHorizontal,
-        y_tick: f64,
-        tick_x: f64,
-    ) {
-        let (_, y) = p.project_point::<StaticPoint2d>(&(0.0, y_tick).into());
-        let label = &format!("{}", y_tick);
-        match tick_side_horizontal {
-            TickSideHorizontal::Right => {
-                ctx.begin_path();
-                ctx.move_to(tick_x, y);
-                ctx.line_to(tick_x + 5.0, y);
-                ctx.stroke();
-                ctx.fill_text(label, tick_x + 10.0, y).unwrap();
-            }
-            TickSideHorizontal::Left => {
-                let text_metrics = ctx.measure_text(label).unwrap();
+        AnyGeometry::Lines(lines) => {
+            let mut iter = lines.points();
+            if let Some(start_pt) = iter.next() {
+                let (x, y) = p.project_point(start_pt);
                 ctx.begin_path();
-                ctx.move_to(tick_x, y);
-                ctx.line_to(tick_x - 5.0, y);
+                ctx.set_stroke_style(&style);
+                ctx.move_to(x, y);
+                for next_pt in iter {
+                    let (x, y) = p.project_point(next_pt);
+                    ctx.line_to(x, y);
+                }
                 ctx.stroke();
-                ctx.fill_text(label, tick_x - 10.0 - text_metrics.width(), y)
-                    .unwrap();
             }
         }
-    }
-}
-
-fn find_optimal_step(step: f64) -> f64 {
-    let log10 = step.log10();
-    let floor = log10.floor();
-    let fract = log10 - floor;
-    let scale = if fract < 0.17 {
-        1.0
-    } else if fract < 0.5 {
-        2.0
-    } else if fract < 0.85 {
-        5.0
-    } else {
-        10.0
-    };
-    10.0.pow(floor) * scale
-}
-
-#[derive(Clone, PartialEq, Debug)]
-pub struct Figure {
-    style: CssStyle,
-    geometry: AnyGeometry,
-}
-
-impl Figure {
-    pub(crate) fn marker<P: Point2d>(style: CssStyle, pt: P) -> Figure {
-        Self {
-            style,
-            geometry: AnyGeometry::HoverMarker(pt.coordinates()),
+        AnyGeometry::HoverMarker(pt) => {
+            let (x, y) = p.project_point(pt);
+            ctx.begin_path();
+            ctx.set_stroke_style(&style);
+            ctx.set_fill_style(&style);
+            ctx.arc(x, y, 5.0, 0.0, PI * 2.0).expect("Infallible");
+            ctx.fill();
+            ctx.stroke();
         }
-    }
-}
-
-impl Figure {
-    pub fn polygon(style: CssStyle, polygon: AnyPolygon<StaticPoint2d>) -> Self {
-        Self {
-            style,
-            geometry: AnyGeometry::Polygon(polygon),
+        AnyGeometry::SnapMarker(pt) => {
+            let (x, y) = p.project_point(pt);
+            ctx.begin_path();
+            ctx.set_stroke_style(&style);
+            ctx.stroke_rect(x - 7.0, y - 7.0, 14.0, 14.0);
+            ctx.move_to(x - 10.0, y);
+            ctx.line_to(x + 10.0, y);
+            ctx.move_to(x, y - 10.0);
+            ctx.line_to(x, y + 10.0);
+            ctx.stroke();
         }
-    }
-    pub fn lines(style: CssStyle, lines: Vec<StaticPoint2d>) -> Self {
-        Self {
-            style,
-            geometry: AnyGeometry::Lines(lines),
+        AnyGeometry::Label { anchor, text } => {
+            let (x, y) = p.project_point(anchor);
+            let (dx, dy) = LABEL_OFFSET;
+            ctx.set_fill_style(&style);
+            ctx.fill_text(text, x + dx, y + dy).unwrap();
         }
-    }
-    fn bbox(&self) -> BoundingBox {
-        self.geometry.bounding_box()
-    }
-    fn draw(&self, ctx: &mut CanvasRenderingContext2d, p: &ScreenProject2d) {
-        match &self.geometry {
-            AnyGeometry::Polygon(polygon) => {
-                let mut iter = polygon.points();
-                if let Some(start_pt) = iter.next() {
-                    let (x, y) = p.project_point(start_pt);
-                    ctx.begin_path();
-                    ctx.set_stroke_style(&self.style.value());
-                    ctx.move_to(x, y);
-                    for next_pt in iter {
-                        let (x, y) = p.project_point(next_pt);
-                        ctx.line_to(x, y);
-                    }
-                    ctx.close_path();
-                    ctx.stroke();
-                }
+        AnyGeometry::Arrow { from, to } => {
+            let (from, to) = (p.project_point(from), p.project_point(to));
+            ctx.set_stroke_style(&style);
+            stroke_lines(ctx, [(from, to)].into_iter().chain(arrow_head(from, to)));
+        }
+        AnyGeometry::Dimension { from, to } => {
+            let layout = dimension_layout(p.project_point(from), p.project_point(to));
+            ctx.set_stroke_style(&style);
+            ctx.set_fill_style(&style);
+            stroke_lines(ctx, layout.lines);
+            let (x, y) = layout.label;
+            ctx.set_text_align("center");
+            ctx.fill_text(&AnyGeometry::dimension_text(from, to), x, y)
+                .unwrap();
+            ctx.set_text_align("start");
+        }
+        AnyGeometry::Circle { center, radius } => {
+            ctx.begin_path();
+            ctx.set_stroke_style(&style);
+            ellipse(ctx, p, center, *radius, 0.0, PI * 2.0);
+            ctx.stroke();
+        }
+        AnyGeometry::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        } => {
+            ctx.begin_path();
+            ctx.set_stroke_style(&style);
+            let sweep = arc_sweep(*start_angle, *end_angle);
+            ellipse(ctx, p, center, *radius, *start_angle, start_angle + sweep);
+            ctx.stroke();
+        }
+        AnyGeometry::Points { points, size } => {
+            ctx.begin_path();
+            ctx.set_fill_style(&style);
+            for pt in points {
+                let (x, y) = p.project_point(pt);
+                ctx.rect(x - size / 2.0, y - size / 2.0, *size, *size);
             }
-            AnyGeometry::Lines(lines) => {
-                let mut iter = lines.points();
-                if let Some(start_pt) = iter.next() {
-                    let (x, y) = p.project_point(start_pt);
-                    ctx.begin_path();
-                    ctx.set_stroke_style(&self.style.value());
-                    ctx.move_to(x, y);
-                    for next_pt in iter {
-                        let (x, y) = p.project_point(next_pt);
-                        ctx.line_to(x, y);
-                    }
-                    ctx.stroke();
+            ctx.fill();
+        }
+        AnyGeometry::HeatMap { .. } => {
+            ctx.set_fill_style(&style);
+            for (x, y, width, height, value) in figure.geometry().heat_map_cells() {
+                if value <= 0.0 {
+                    continue;
                 }
+                let (x1, y1) = p.project_point::<StaticPoint2d>(&(x, y).into());
+                let (x2, y2) = p.project_point::<StaticPoint2d>(&(x + width, y + height).into());
+                ctx.set_global_alpha(value.min(1.0) * HEAT_MAP_OPACITY);
+                ctx.fill_rect(x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs());
             }
-            AnyGeometry::HoverMarker(pt) => {
-                let (x, y) = p.project_point(pt);
-                ctx.begin_path();
-                ctx.set_stroke_style(&self.style.value());
-                ctx.set_fill_style(&self.style.value());
-                ctx.arc(x, y, 5.0, 0.0, PI * 2.0).expect("Infallible");
-                ctx.fill();
-                ctx.stroke();
-            }
+            ctx.set_global_alpha(1.0);
         }
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
-pub enum CssStyle {
-    Color(CssColor),
+/// Adds a counter-clockwise world arc to the current path, it becomes an ellipse
+/// if the axes are scaled differently
+fn ellipse(
+    ctx: &CanvasRenderingContext2d,
+    p: &ScreenProject2d,
+    center: &StaticPoint2d,
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+) {
+    let (x, y) = p.project_point(center);
+    let (rx, ry) = (radius * p.x_scale().0, radius * p.y_scale().0);
+    let (start_angle, end_angle, anticlockwise) = match p.y_axis() {
+        YAxis::Up => (-start_angle, -end_angle, true),
+        YAxis::Down => (start_angle, end_angle, false),
+    };
+    ctx.ellipse_with_anticlockwise(x, y, rx, ry, 0.0, start_angle, end_angle, anticlockwise)
+        .expect("Infallible");
 }
 
-impl CssStyle {
-    fn value(&self) -> JsValue {
-        match self {
-            Color(c) => c.value(),
-        }
+fn stroke_lines(
+    ctx: &CanvasRenderingContext2d,
+    lines: impl IntoIterator<Item = ((f64, f64), (f64, f64))>,
+) {
+    ctx.begin_path();
+    for ((x1, y1), (x2, y2)) in lines {
+        ctx.move_to(x1, y1);
+        ctx.line_to(x2, y2);
     }
+    ctx.stroke();
 }
 
-impl Default for CssStyle {
-    fn default() -> Self {
-        Color(CssColor::Black)
-    }
-}
+/// Layer of the `overlay` property, above any other layer a page is expected to use
+pub const OVERLAY_Z: i32 = 1000;
 
-#[derive(Clone, PartialEq, Debug, Default)]
-pub enum CssColor {
-    #[default]
-    Black,
-    Blue,
-    Green,
-    Red,
-}
+#[derive(Clone)]
+pub struct PolygonList(Rc<[Figure]>);
 
-impl CssColor {
-    fn value(&self) -> JsValue {
-        match self {
-            CssColor::Black => JsValue::from_str("black"),
-            CssColor::Blue => JsValue::from_str("blue"),
-            CssColor::Green => JsValue::from_str("green"),
-            CssColor::Red => JsValue::from_str("red"),
-        }
-    }
-}
+impl PolygonList {}
 
-#[derive(Clone, PartialEq, Debug)]
-pub enum AnyGeometry {
-    Polygon(AnyPolygon<StaticPoint2d>),
-    Lines(Vec<StaticPoint2d>),
-    HoverMarker(StaticPoint2d),
+impl PartialEq for PolygonList {
+    fn eq(&self, other: &Self) -> bool {
+        // memoized lists are shared, which spares comparing every figure
+        Rc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+    }
 }
 
-impl AnyGeometry {
-    fn bounding_box(&self) -> BoundingBox {
-        let mut bbox = BoundingBox::default();
-        match self {
-            AnyGeometry::Polygon(p) => {
-                for p in p.points() {
-                    bbox += *p;
-                }
-            }
-            AnyGeometry::Lines(l) => {
-                for p in l {
-                    bbox += *p;
-                }
-            }
-            AnyGeometry::HoverMarker(p) => {
-                bbox += *p;
-            }
-        }
-        bbox
+impl Default for PolygonList {
+    fn default() -> Self {
+        Vec::new().into()
     }
 }
 
-#[derive(Clone, PartialEq)]
-pub struct PolygonList(Rc<[Figure]>);
-
-impl PolygonList {}
-
 impl IntoPropValue<Rc<[Figure]>> for PolygonList {
     fn into_prop_value(self) -> Rc<[Figure]> {
         self.0
@@ -439,7 +292,18 @@ impl From<Vec<Figure>> for PolygonList {
 
 #[derive(Properties, PartialEq)]
 pub struct RenderProperties {
+    /// Drawn as a layer at z 0
+    #[prop_or_default]
     pub polygons: PolygonList,
+    /// Hover and snap markers and the like, which change without the polygons changing.
+    /// Drawn on top of everything else and never fitted into the view.
+    #[prop_or_default]
+    pub overlay: PolygonList,
+    /// Further layers, in addition to `polygons` and `overlay`
+    #[prop_or_default]
+    pub layers: Layers,
+    #[prop_or_default]
+    pub projection: ProjectionConfig,
     pub on_mouse_event: Option<Callback<CanvasMouseEvent>>,
 }
 
@@ -449,6 +313,10 @@ pub struct CanvasMouseEvent {
     y: Number,
     buttons: u16,
     resolution: Number,
+    grid_step: (f64, f64),
+    shift_key: bool,
+    ctrl_key: bool,
+    alt_key: bool,
 }
 
 impl CanvasMouseEvent {
@@ -468,23 +336,58 @@ impl CanvasMouseEvent {
     pub fn resolution(&self) -> Number {
         self.resolution
     }
+    /// World distance between the drawn grid ticks on the x and y axis
+    #[inline]
+    pub fn grid_step(&self) -> (f64, f64) {
+        self.grid_step
+    }
+    #[inline]
+    pub fn shift_key(&self) -> bool {
+        self.shift_key
+    }
+    #[inline]
+    pub fn ctrl_key(&self) -> bool {
+        self.ctrl_key
+    }
+    #[inline]
+    pub fn alt_key(&self) -> bool {
+        self.alt_key
+    }
 }
 
 #[function_component(Render2d)]
 pub fn render_2d(properties: &RenderProperties) -> Html {
-    let last_projection = Rc::new(RefCell::new(None::<ScreenProject2d>));
+    // drawing happens in a later animation frame, mouse events in between need the
+    // projection of the last drawn frame
+    let last_projection = use_mut_ref(|| None::<ScreenProject2d>);
+    let theme = use_theme();
     let current_projection = last_projection.clone();
+    let mut layers = Layers::default()
+        .with(Layer::new("figures", 0, properties.polygons.0.clone()))
+        .with(Layer::overlay(
+            "overlay",
+            OVERLAY_Z,
+            properties.overlay.0.clone(),
+        ));
+    layers.extend(&properties.layers);
     let onmouse = properties.on_mouse_event.clone().map(|mouse_callback| {
         Callback::from(move |mouse_event: MouseEvent| {
             if let Some(p) = current_projection.borrow().deref() {
-                let (x, y) = p.find_origin_point(mouse_event.offset_x(), mouse_event.offset_y());
+                let (x, y) = p.find_origin_point(
+                    mouse_event.offset_x() as f64,
+                    mouse_event.offset_y() as f64,
+                );
 
                 let buttons = mouse_event.buttons();
                 mouse_callback.emit(CanvasMouseEvent {
                     x,
                     y,
                     buttons,
-                    resolution: Number::one() / p.scale,
+                    resolution: Number::one() / p.scale(),
+                    grid_step: grid_steps(p),
+                    shift_key: mouse_event.shift_key(),
+                    ctrl_key: mouse_event.ctrl_key(),
+                    alt_key: mouse_event.alt_key(),
                 })
             } else {
                 info!("no projection");
@@ -496,7 +399,12 @@ pub fn render_2d(properties: &RenderProperties) -> Html {
             <Canvas<CanvasRenderingContext2d, Render>
                 {onmouse}
                 //send props when create a Render
-                render={Box::new(Render{display_list:properties.polygons.0.clone(), last_projection})}
+                render={Box::new(Render{
+                    layers,
+                    projection:properties.projection.clone(),
+                    theme,
+                    last_projection
+                })}
             >
                 {"The browser is not supported."}
             </Canvas<CanvasRenderingContext2d, Render >>
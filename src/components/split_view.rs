@@ -0,0 +1,84 @@
+//! Side by side [`Render2d`] panels showing the same world region with a common cursor
+
+use triangle_play::display::{CssColor, CssStyle, Figure, Layer, Layers};
+use triangles::prelude::StaticPoint2d;
+use yew::{function_component, html, use_state, AttrValue, Callback, Html, Properties};
+
+use crate::components::render2d::{
+    CanvasMouseEvent, PolygonList, ProjectionConfig, Render2d, OVERLAY_Z,
+};
+
+/// One view of a [`SplitView`], e.g. a stage of a computation or one of its variants
+#[derive(Clone, PartialEq, Debug)]
+pub struct Panel {
+    pub title: AttrValue,
+    pub layers: Layers,
+}
+
+impl Panel {
+    pub fn new(title: impl Into<AttrValue>, layers: Layers) -> Self {
+        Self {
+            title: title.into(),
+            layers,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SplitViewProperties {
+    pub panels: Vec<Panel>,
+    /// Drawn on top of every panel, e.g. the handles of an editor
+    #[prop_or_default]
+    pub overlay: PolygonList,
+    /// Used by every panel, without world bounds they fit the contents of all panels
+    #[prop_or_default]
+    pub projection: ProjectionConfig,
+    /// Mouse events of any panel, the panels share their world coordinates
+    pub on_mouse_event: Option<Callback<CanvasMouseEvent>>,
+}
+
+#[function_component(SplitView)]
+pub fn split_view(properties: &SplitViewProperties) -> Html {
+    let cursor = use_state(|| None::<StaticPoint2d>);
+    let projection = properties.projection.shared(
+        properties
+            .panels
+            .iter()
+            .map(|panel| panel.layers.fit_bbox()),
+    );
+    let on_mouse_event = {
+        let cursor = cursor.clone();
+        let on_mouse_event = properties.on_mouse_event.clone();
+        Callback::from(move |event: CanvasMouseEvent| {
+            let position = Some((event.x(), event.y()).into());
+            if *cursor != position {
+                cursor.set(position);
+            }
+            if let Some(on_mouse_event) = &on_mouse_event {
+                on_mouse_event.emit(event);
+            }
+        })
+    };
+    // the panel under the mouse shows it anyway, the others show where it is
+    let cursor_figures: Vec<Figure> = cursor
+        .iter()
+        .map(|pt| Figure::marker(CssStyle::Color(CssColor::Black), *pt))
+        .collect();
+    let cursor_layer = Layer::overlay("cursor", OVERLAY_Z, cursor_figures);
+
+    html! {
+        <div class="split-view">
+            { for properties.panels.iter().map(|panel| html! {
+                <div class="split-view-panel">
+                    <div>{panel.title.clone()}</div>
+                    <Render2d
+                        layers={panel.layers.clone().with(cursor_layer.clone())}
+                        overlay={properties.overlay.clone()}
+                        projection={projection.clone()}
+                        on_mouse_event={on_mouse_event.clone()}
+                    />
+                </div>
+            })}
+        </div>
+    }
+}
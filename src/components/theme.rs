@@ -0,0 +1,83 @@
+//! Light or dark theme of the page chrome and of every [`Render2d`](super::render2d::Render2d)
+
+use gloo::utils::document_element;
+use patternfly_yew::prelude::Switch;
+use triangle_play::render::theme::Theme;
+use yew::{
+    function_component, hook, html, use_context, use_effect_with_deps, use_state, Callback,
+    Children, ContextProvider, Html, Properties, UseStateHandle,
+};
+
+/// Class switching the PatternFly components to their dark variant
+const DARK_THEME_CLASS: &str = "pf-v5-theme-dark";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct ThemeChoice {
+    pub dark: bool,
+    pub colorblind: bool,
+    pub grid: bool,
+}
+
+impl ThemeChoice {
+    pub fn theme(&self) -> Theme {
+        Theme::select(self.dark, self.colorblind, self.grid)
+    }
+}
+
+pub type ThemeContext = UseStateHandle<ThemeChoice>;
+
+/// Theme of the enclosing [`ThemeProvider`], the light one outside of it
+#[hook]
+pub fn use_theme() -> Theme {
+    use_context::<ThemeContext>()
+        .map(|choice| choice.theme())
+        .unwrap_or_default()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ThemeProviderProperties {
+    pub children: Children,
+}
+
+/// Keeps the theme choice and applies it to the page
+#[function_component(ThemeProvider)]
+pub fn theme_provider(properties: &ThemeProviderProperties) -> Html {
+    let choice = use_state(ThemeChoice::default);
+    use_effect_with_deps(
+        |dark| {
+            let _ = document_element()
+                .class_list()
+                .toggle_with_force(DARK_THEME_CLASS, *dark);
+        },
+        choice.dark,
+    );
+    html! {
+        <ContextProvider<ThemeContext> context={choice}>
+            { for properties.children.iter() }
+        </ContextProvider<ThemeContext>>
+    }
+}
+
+/// Switches for the sidebar
+#[function_component(ThemeSwitches)]
+pub fn theme_switches() -> Html {
+    let choice = use_context::<ThemeContext>();
+    let Some(choice) = choice else {
+        return html! {};
+    };
+    let toggle = |update: fn(&mut ThemeChoice, bool)| {
+        let choice = choice.clone();
+        Callback::from(move |checked: bool| {
+            let mut next_choice = *choice;
+            update(&mut next_choice, checked);
+            choice.set(next_choice);
+        })
+    };
+    html! {
+        <>
+            <Switch label="Dark mode" checked={choice.dark} onchange={toggle(|c, v| c.dark = v)}/>
+            <Switch label="Colorblind colors" checked={choice.colorblind} onchange={toggle(|c, v| c.colorblind = v)}/>
+            <Switch label="Grid lines" checked={choice.grid} onchange={toggle(|c, v| c.grid = v)}/>
+        </>
+    }
+}
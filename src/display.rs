@@ -0,0 +1,505 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::rc::Rc;
+
+use triangles::prelude::{AnyPolygon, BoundingBox, Point2d, Polygon2d, StaticPoint2d};
+
+use crate::display::CssStyle::Color;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Figure {
+    style: CssStyle,
+    geometry: AnyGeometry,
+}
+
+impl Figure {
+    pub fn marker<P: Point2d>(style: CssStyle, pt: P) -> Figure {
+        Self {
+            style,
+            geometry: AnyGeometry::HoverMarker(pt.coordinates()),
+        }
+    }
+    pub fn snap_marker<P: Point2d>(style: CssStyle, pt: P) -> Figure {
+        Self {
+            style,
+            geometry: AnyGeometry::SnapMarker(pt.coordinates()),
+        }
+    }
+}
+
+impl Figure {
+    pub fn polygon(style: CssStyle, polygon: AnyPolygon<StaticPoint2d>) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Polygon(polygon),
+        }
+    }
+    pub fn lines(style: CssStyle, lines: Vec<StaticPoint2d>) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Lines(lines),
+        }
+    }
+    /// Text drawn next to a world point
+    pub fn label<P: Point2d>(style: CssStyle, anchor: P, text: impl Into<String>) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Label {
+                anchor: anchor.coordinates(),
+                text: text.into(),
+            },
+        }
+    }
+    /// Labels `{prefix}1`, `{prefix}2`, ... at the given points
+    pub fn vertex_labels<'a, P: Point2d + 'a>(
+        style: CssStyle,
+        prefix: &str,
+        points: impl IntoIterator<Item = &'a P>,
+    ) -> Vec<Self> {
+        points
+            .into_iter()
+            .enumerate()
+            .map(|(idx, pt)| {
+                Self::label(
+                    style.clone(),
+                    pt.coordinates(),
+                    format!("{prefix}{}", idx + 1),
+                )
+            })
+            .collect()
+    }
+    pub fn arrow<P: Point2d>(style: CssStyle, from: P, to: P) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Arrow {
+                from: from.coordinates(),
+                to: to.coordinates(),
+            },
+        }
+    }
+    /// Dimension line labeled with the distance of the two points
+    pub fn dimension<P: Point2d>(style: CssStyle, from: P, to: P) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Dimension {
+                from: from.coordinates(),
+                to: to.coordinates(),
+            },
+        }
+    }
+    pub fn circle<P: Point2d>(style: CssStyle, center: P, radius: f64) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Circle {
+                center: center.coordinates(),
+                radius,
+            },
+        }
+    }
+    /// Arc running counter-clockwise from `start_angle` to `end_angle`, angles in radians
+    pub fn arc<P: Point2d>(
+        style: CssStyle,
+        center: P,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Arc {
+                center: center.coordinates(),
+                radius,
+                start_angle,
+                end_angle,
+            },
+        }
+    }
+    /// Many small square markers sharing one style, `size` is in pixels
+    pub fn points(style: CssStyle, points: Vec<StaticPoint2d>, size: f64) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::Points { points, size },
+        }
+    }
+    /// Grid of translucent cells with `columns` cells per row, starting at the lower
+    /// left corner `origin`, the values between 0 and 1 set the opacity of the cells
+    pub fn heat_map<P: Point2d>(
+        style: CssStyle,
+        origin: P,
+        cell_size: (f64, f64),
+        columns: usize,
+        values: Vec<f64>,
+    ) -> Self {
+        Self {
+            style,
+            geometry: AnyGeometry::HeatMap {
+                origin: origin.coordinates(),
+                cell_size,
+                columns,
+                values,
+            },
+        }
+    }
+    pub fn style(&self) -> &CssStyle {
+        &self.style
+    }
+    pub fn geometry(&self) -> &AnyGeometry {
+        &self.geometry
+    }
+    pub fn bbox(&self) -> BoundingBox {
+        self.geometry.bounding_box()
+    }
+}
+
+/// Named group of figures which is drawn, hidden and fitted into the view as a whole
+#[derive(Clone, Debug)]
+pub struct Layer {
+    pub name: &'static str,
+    /// Layers with a higher z are drawn on top, equal ones in the order they were added
+    pub z: i32,
+    pub visible: bool,
+    /// Whether the view is fitted to the figures, off for markers following the mouse
+    pub fit: bool,
+    pub figures: Rc<[Figure]>,
+}
+
+impl Layer {
+    pub fn new(name: &'static str, z: i32, figures: impl Into<Rc<[Figure]>>) -> Self {
+        Self {
+            name,
+            z,
+            visible: true,
+            fit: true,
+            figures: figures.into(),
+        }
+    }
+
+    /// A layer which never changes the projection
+    pub fn overlay(name: &'static str, z: i32, figures: impl Into<Rc<[Figure]>>) -> Self {
+        Self {
+            fit: false,
+            ..Self::new(name, z, figures)
+        }
+    }
+
+    pub fn with_visible(self, visible: bool) -> Self {
+        Self { visible, ..self }
+    }
+}
+
+impl PartialEq for Layer {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.z == other.z
+            && self.visible == other.visible
+            && self.fit == other.fit
+            // memoized figures are shared, which spares comparing each of them
+            && (Rc::ptr_eq(&self.figures, &other.figures) || self.figures == other.figures)
+    }
+}
+
+/// Layers of a view, see [`Layer`]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Layers(Vec<Layer>);
+
+impl Layers {
+    pub fn with(mut self, layer: Layer) -> Self {
+        self.0.push(layer);
+        self
+    }
+
+    pub fn push(&mut self, layer: Layer) {
+        self.0.push(layer);
+    }
+
+    pub fn extend(&mut self, layers: &Layers) {
+        self.0.extend(layers.0.iter().cloned());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Layer> {
+        self.0.iter().find(|layer| layer.name == name)
+    }
+
+    pub fn set_visible(&mut self, name: &str, visible: bool) {
+        for layer in self.0.iter_mut().filter(|layer| layer.name == name) {
+            layer.visible = visible;
+        }
+    }
+
+    /// Visible layers from bottom to top
+    pub fn drawn(&self) -> Vec<&Layer> {
+        let mut layers: Vec<_> = self.0.iter().filter(|layer| layer.visible).collect();
+        // stable, so equal z keeps the order of insertion
+        layers.sort_by_key(|layer| layer.z);
+        layers
+    }
+
+    /// Figures of the visible layers in drawing order
+    pub fn figures(&self) -> impl Iterator<Item = &Figure> {
+        self.drawn()
+            .into_iter()
+            .flat_map(|layer| layer.figures.iter())
+    }
+
+    /// Bounding box the view is fitted to, over the visible layers which take part in it
+    pub fn fit_bbox(&self) -> BoundingBox {
+        let mut bbox = BoundingBox::default();
+        for layer in self.0.iter().filter(|layer| layer.visible && layer.fit) {
+            for figure in layer.figures.iter() {
+                bbox += figure.bbox();
+            }
+        }
+        bbox
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum CssStyle {
+    Color(CssColor),
+}
+
+impl CssStyle {
+    /// Value as accepted by the stroke and fill styles of a canvas or svg
+    pub fn css_value(&self) -> &'static str {
+        match self {
+            Color(c) => c.css_name(),
+        }
+    }
+}
+
+impl Default for CssStyle {
+    fn default() -> Self {
+        Color(CssColor::Black)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum CssColor {
+    #[default]
+    Black,
+    Blue,
+    Green,
+    Red,
+}
+
+impl CssColor {
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            CssColor::Black => "black",
+            CssColor::Blue => "blue",
+            CssColor::Green => "green",
+            CssColor::Red => "red",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum AnyGeometry {
+    Polygon(AnyPolygon<StaticPoint2d>),
+    Lines(Vec<StaticPoint2d>),
+    HoverMarker(StaticPoint2d),
+    SnapMarker(StaticPoint2d),
+    Label {
+        anchor: StaticPoint2d,
+        text: String,
+    },
+    Arrow {
+        from: StaticPoint2d,
+        to: StaticPoint2d,
+    },
+    Dimension {
+        from: StaticPoint2d,
+        to: StaticPoint2d,
+    },
+    Circle {
+        center: StaticPoint2d,
+        radius: f64,
+    },
+    Arc {
+        center: StaticPoint2d,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    },
+    Points {
+        points: Vec<StaticPoint2d>,
+        size: f64,
+    },
+    HeatMap {
+        origin: StaticPoint2d,
+        cell_size: (f64, f64),
+        columns: usize,
+        values: Vec<f64>,
+    },
+}
+
+impl AnyGeometry {
+    /// Text of the dimension label
+    pub fn dimension_text(from: &StaticPoint2d, to: &StaticPoint2d) -> String {
+        let (dx, dy) = (to.x.0 - from.x.0, to.y.0 - from.y.0);
+        format!("{:.2}", dx.hypot(dy))
+    }
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut bbox = BoundingBox::default();
+        match self {
+            AnyGeometry::Polygon(p) => {
+                for p in p.points() {
+                    bbox += *p;
+                }
+            }
+            AnyGeometry::Lines(l) => {
+                for p in l {
+                    bbox += *p;
+                }
+            }
+            AnyGeometry::HoverMarker(p)
+            | AnyGeometry::SnapMarker(p)
+            | AnyGeometry::Label { anchor: p, .. } => {
+                bbox += *p;
+            }
+            AnyGeometry::Arrow { from, to } | AnyGeometry::Dimension { from, to } => {
+                bbox += *from;
+                bbox += *to;
+            }
+            AnyGeometry::Circle { center, radius } => {
+                let (x, y) = (center.x.0, center.y.0);
+                bbox += StaticPoint2d::from((x - radius, y - radius));
+                bbox += StaticPoint2d::from((x + radius, y + radius));
+            }
+            AnyGeometry::Arc {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                let (x, y) = (center.x.0, center.y.0);
+                for angle in arc_extreme_angles(*start_angle, *end_angle) {
+                    let (sin, cos) = angle.sin_cos();
+                    bbox += StaticPoint2d::from((x + radius * cos, y + radius * sin));
+                }
+            }
+            AnyGeometry::Points { points, .. } => {
+                for p in points {
+                    bbox += *p;
+                }
+            }
+            AnyGeometry::HeatMap { .. } => {
+                for (x, y, width, height, _) in self.heat_map_cells() {
+                    bbox += StaticPoint2d::from((x, y));
+                    bbox += StaticPoint2d::from((x + width, y + height));
+                }
+            }
+        }
+        bbox
+    }
+    /// Lower left corner, size and value of every cell of a heat map
+    pub fn heat_map_cells(&self) -> Vec<(f64, f64, f64, f64, f64)> {
+        match self {
+            AnyGeometry::HeatMap {
+                origin,
+                cell_size: (width, height),
+                columns,
+                values,
+            } => values
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| {
+                    let columns = (*columns).max(1);
+                    let (row, column) = (idx / columns, idx % columns);
+                    (
+                        origin.x.0 + column as f64 * width,
+                        origin.y.0 + row as f64 * height,
+                        *width,
+                        *height,
+                        *value,
+                    )
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Opacity of fully saturated heat map cells, keeps the figures above readable
+pub const HEAT_MAP_OPACITY: f64 = 0.6;
+
+/// Counter-clockwise sweep of an arc in radians, between 0 and a full turn
+pub fn arc_sweep(start_angle: f64, end_angle: f64) -> f64 {
+    let sweep = end_angle - start_angle;
+    if sweep >= TAU {
+        TAU
+    } else {
+        sweep.rem_euclid(TAU)
+    }
+}
+
+/// Angles of the arc end points and of every axis crossing in between
+fn arc_extreme_angles(start_angle: f64, end_angle: f64) -> Vec<f64> {
+    let sweep = arc_sweep(start_angle, end_angle);
+    let mut angles = vec![start_angle, start_angle + sweep];
+    let mut quadrant = (start_angle / FRAC_PI_2).floor() + 1.0;
+    while quadrant * FRAC_PI_2 < start_angle + sweep {
+        angles.push(quadrant * FRAC_PI_2);
+        quadrant += 1.0;
+    }
+    angles
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use triangles::prelude::{BoundingBox, StaticPoint2d};
+
+    use crate::display::{AnyGeometry, CssStyle, Figure, Layer, Layers};
+
+    fn pt(x: f64, y: f64) -> StaticPoint2d {
+        (x, y).into()
+    }
+
+    fn extent(figure: &Figure) -> (f64, f64, f64, f64) {
+        match figure.bbox() {
+            BoundingBox::Empty => panic!("empty bounding box"),
+            BoundingBox::Box(b) => (b.min_x().0, b.min_y().0, b.max_x().0, b.max_y().0),
+        }
+    }
+
+    #[test]
+    fn test_arc_bounding_box() {
+        let upper_half = Figure::arc(CssStyle::default(), pt(1.0, 1.0), 2.0, 0.0, PI);
+        let (min_x, min_y, max_x, max_y) = extent(&upper_half);
+        assert_eq!((min_x, max_x, max_y), (-1.0, 3.0, 3.0));
+        assert!((min_y - 1.0).abs() < 1e-12);
+
+        let wrapping = Figure::arc(CssStyle::default(), pt(0.0, 0.0), 1.0, -0.1, 0.1);
+        let (min_x, _, max_x, _) = extent(&wrapping);
+        assert_eq!(max_x, 1.0);
+        assert!(min_x > 0.99);
+
+        let circle = Figure::circle(CssStyle::default(), pt(0.0, 0.0), 3.0);
+        assert_eq!(extent(&circle), (-3.0, -3.0, 3.0, 3.0));
+        assert!(matches!(circle.geometry(), AnyGeometry::Circle { .. }));
+    }
+
+    #[test]
+    fn test_layers() {
+        let marker = |x: f64| Figure::marker(CssStyle::default(), pt(x, 0.0));
+        let mut layers = Layers::default()
+            .with(Layer::overlay("cursor", 10, vec![marker(100.0)]))
+            .with(Layer::new("grid", -1, vec![marker(-1.0)]))
+            .with(Layer::new("cut", 0, vec![marker(0.0)]))
+            .with(Layer::new("labels", 0, vec![marker(1.0)]));
+        let names: Vec<_> = layers.drawn().iter().map(|layer| layer.name).collect();
+        assert_eq!(names, ["grid", "cut", "labels", "cursor"]);
+        assert_eq!(layers.figures().count(), 4);
+        // the overlay is drawn but leaves the view alone
+        assert!(matches!(
+            layers.fit_bbox(),
+            BoundingBox::Box(b) if (b.min_x().0, b.max_x().0) == (-1.0, 1.0)
+        ));
+
+        layers.set_visible("grid", false);
+        assert_eq!(layers.drawn()[0].name, "cut");
+        assert!(!layers.get("grid").unwrap().visible);
+        assert!(matches!(layers.fit_bbox(), BoundingBox::Box(b) if b.min_x().0 == 0.0));
+    }
+}
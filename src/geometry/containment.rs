@@ -0,0 +1,112 @@
+use num_traits::Zero;
+use triangles::prelude::{
+    BoundingBoxValues, Number, Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d,
+};
+
+/// Position of a point relative to a triangle
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Containment {
+    Inside,
+    Outside,
+    /// On the edge starting at the corner with the given index, excluding its end points
+    OnEdge(usize),
+    /// On the corner with the given index
+    OnVertex(usize),
+}
+
+/// Classifies a point with the orientation predicate of the `triangles` crate.
+///
+/// The point is tested against the three edges `p1 -> p2`, `p2 -> p3` and `p3 -> p1`
+/// independently, so inconsistent orientation results show up as points classified
+/// outside although they lie on an edge, or as edges which miss their end points.
+/// The triangle must not be degenerate.
+pub fn classify<P: Point2d>(triangle: &StaticTriangle2d<StaticPoint2d>, point: &P) -> Containment {
+    let point = point.coordinates();
+    let orientation = triangle.area();
+    let corners = [*triangle.p1(), *triangle.p2(), *triangle.p3()];
+    let sides = [0, 1, 2].map(|idx| {
+        let side = StaticTriangle2d::new(corners[idx], corners[(idx + 1) % 3], point).area();
+        if orientation < Number::zero() {
+            Number::zero() - side
+        } else {
+            side
+        }
+    });
+    if sides.iter().any(|side| *side < Number::zero()) {
+        return Containment::Outside;
+    }
+    let on_edge: Vec<usize> = (0..3).filter(|idx| sides[*idx].is_zero()).collect();
+    match on_edge[..] {
+        [] => Containment::Inside,
+        [edge] => Containment::OnEdge(edge),
+        // two consecutive edges meet at the end point of the first one
+        [0, 1] => Containment::OnVertex(1),
+        [1, 2] => Containment::OnVertex(2),
+        [0, 2] => Containment::OnVertex(0),
+        _ => Containment::Outside,
+    }
+}
+
+/// Classifies a regular grid of `steps + 1` by `steps + 1` points covering `bounds`
+pub fn sample_grid(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    bounds: &BoundingBoxValues,
+    steps: usize,
+) -> Vec<(StaticPoint2d, Containment)> {
+    let steps = steps.max(1);
+    let (min_x, min_y) = (bounds.min_x().0, bounds.min_y().0);
+    let dx = (bounds.max_x().0 - min_x) / steps as f64;
+    let dy = (bounds.max_y().0 - min_y) / steps as f64;
+    (0..=steps)
+        .flat_map(|row| (0..=steps).map(move |column| (row, column)))
+        .map(|(row, column)| {
+            let point = StaticPoint2d::from((min_x + column as f64 * dx, min_y + row as f64 * dy));
+            (point, classify(triangle, &point))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{BoundingBoxValues, StaticPoint2d, StaticTriangle2d};
+
+    use crate::geometry::containment::{classify, sample_grid, Containment};
+
+    fn triangle(points: [(f64, f64); 3]) -> StaticTriangle2d<StaticPoint2d> {
+        let [p1, p2, p3] = points.map(StaticPoint2d::from);
+        StaticTriangle2d::new(p1, p2, p3)
+    }
+
+    #[test]
+    fn test_classify() {
+        let counter_clockwise = triangle([(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]);
+        let clockwise = triangle([(0.0, 0.0), (0.0, 10.0), (10.0, 0.0)]);
+        for (t, edge_x, edge_y) in [(counter_clockwise, 0, 2), (clockwise, 2, 0)] {
+            let at = |x: f64, y: f64| classify(&t, &StaticPoint2d::from((x, y)));
+            assert_eq!(at(2.0, 2.0), Containment::Inside);
+            assert_eq!(at(20.0, 2.0), Containment::Outside);
+            assert_eq!(at(6.0, 6.0), Containment::Outside);
+            assert_eq!(at(5.0, 0.0), Containment::OnEdge(edge_x));
+            assert_eq!(at(0.0, 5.0), Containment::OnEdge(edge_y));
+            assert_eq!(at(5.0, 5.0), Containment::OnEdge(1));
+            assert_eq!(at(0.0, 0.0), Containment::OnVertex(0));
+            assert_eq!(at(-5.0, 0.0), Containment::Outside);
+        }
+        let at = |x: f64, y: f64| classify(&counter_clockwise, &StaticPoint2d::from((x, y)));
+        assert_eq!(at(10.0, 0.0), Containment::OnVertex(1));
+        assert_eq!(at(0.0, 10.0), Containment::OnVertex(2));
+    }
+
+    #[test]
+    fn test_sample_grid() {
+        let t = triangle([(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]);
+        let bounds = BoundingBoxValues::new(0.0.into(), 0.0.into(), 4.0.into(), 4.0.into());
+        let samples = sample_grid(&t, &bounds, 4);
+        assert_eq!(samples.len(), 25);
+        let count = |c: fn(&Containment) -> bool| samples.iter().filter(|(_, s)| c(s)).count();
+        assert_eq!(count(|s| matches!(s, Containment::OnVertex(_))), 3);
+        assert_eq!(count(|s| matches!(s, Containment::OnEdge(_))), 9);
+        assert_eq!(count(|s| *s == Containment::Inside), 3);
+        assert_eq!(count(|s| *s == Containment::Outside), 10);
+    }
+}
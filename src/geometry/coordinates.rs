@@ -0,0 +1,119 @@
+//! Serialization of `triangles` types, which do not implement serde themselves.
+//!
+//! Fields holding points or triangles are annotated with
+//! `#[serde(with = "crate::geometry::coordinates")]` and written as coordinate tuples,
+//! like the scene files write them.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use triangles::prelude::{BoundingBoxValues, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+/// Value which is serialized as its plain coordinates
+pub trait Coordinates: Sized {
+    type Plain: Serialize + DeserializeOwned;
+
+    fn to_plain(&self) -> Self::Plain;
+    fn from_plain(plain: Self::Plain) -> Self;
+}
+
+impl Coordinates for StaticPoint2d {
+    type Plain = (f64, f64);
+
+    fn to_plain(&self) -> Self::Plain {
+        (self.x.0, self.y.0)
+    }
+    fn from_plain(plain: Self::Plain) -> Self {
+        plain.into()
+    }
+}
+
+impl Coordinates for StaticTriangle2d<StaticPoint2d> {
+    type Plain = [(f64, f64); 3];
+
+    fn to_plain(&self) -> Self::Plain {
+        [self.p1(), self.p2(), self.p3()].map(StaticPoint2d::to_plain)
+    }
+    fn from_plain([p1, p2, p3]: Self::Plain) -> Self {
+        StaticTriangle2d::new(p1.into(), p2.into(), p3.into())
+    }
+}
+
+impl Coordinates for BoundingBoxValues {
+    /// Minimum x and y, then maximum x and y
+    type Plain = [f64; 4];
+
+    fn to_plain(&self) -> Self::Plain {
+        [self.min_x(), self.min_y(), self.max_x(), self.max_y()].map(|value| value.0)
+    }
+    fn from_plain([min_x, min_y, max_x, max_y]: Self::Plain) -> Self {
+        BoundingBoxValues::new(min_x.into(), min_y.into(), max_x.into(), max_y.into())
+    }
+}
+
+impl<T: Coordinates> Coordinates for Vec<T> {
+    type Plain = Vec<T::Plain>;
+
+    fn to_plain(&self) -> Self::Plain {
+        self.iter().map(T::to_plain).collect()
+    }
+    fn from_plain(plain: Self::Plain) -> Self {
+        plain.into_iter().map(T::from_plain).collect()
+    }
+}
+
+/// A value along with a distance or another measure of it
+impl<T: Coordinates> Coordinates for (T, f64) {
+    type Plain = (T::Plain, f64);
+
+    fn to_plain(&self) -> Self::Plain {
+        (self.0.to_plain(), self.1)
+    }
+    fn from_plain((value, measure): Self::Plain) -> Self {
+        (T::from_plain(value), measure)
+    }
+}
+
+pub fn serialize<T: Coordinates, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.to_plain().serialize(serializer)
+}
+
+pub fn deserialize<'de, T: Coordinates, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    T::Plain::deserialize(deserializer).map(T::from_plain)
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Shapes {
+        #[serde(with = "crate::geometry::coordinates")]
+        triangle: StaticTriangle2d<StaticPoint2d>,
+        #[serde(with = "crate::geometry::coordinates")]
+        measured: Vec<(StaticPoint2d, f64)>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let shapes = Shapes {
+            triangle: StaticTriangle2d::new(
+                (0.0, 0.0).into(),
+                (0.1, 0.0).into(),
+                (0.0, 1e300).into(),
+            ),
+            measured: vec![((1.5, -2.0).into(), 0.25)],
+        };
+        let json = serde_json::to_string(&shapes).unwrap();
+        assert_eq!(
+            json,
+            r#"{"triangle":[[0.0,0.0],[0.1,0.0],[0.0,1e+300]],"measured":[[[1.5,-2.0],0.25]]}"#
+        );
+        assert_eq!(serde_json::from_str::<Shapes>(&json).unwrap(), shapes);
+    }
+}
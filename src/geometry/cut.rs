@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Polygon2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+/// Pieces of a triangle after cutting it with another triangle
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct CutResult {
+    /// Parts covered by the cutter
+    #[serde(with = "crate::geometry::coordinates")]
+    pub inside: Vec<StaticTriangle2d<StaticPoint2d>>,
+    /// Parts not covered by the cutter
+    #[serde(with = "crate::geometry::coordinates")]
+    pub outside: Vec<StaticTriangle2d<StaticPoint2d>>,
+}
+
+impl CutResult {
+    pub fn inside_area(&self) -> f64 {
+        total_area(&self.inside)
+    }
+    pub fn outside_area(&self) -> f64 {
+        total_area(&self.outside)
+    }
+}
+
+/// Runs [`Triangle2d::cut_to_triangles`] the way the cut page displays it
+pub fn cut_triangle(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+) -> CutResult {
+    let triangles = triangle.cut_to_triangles(cutter);
+    let mut sides = [Vec::new(), Vec::new()];
+    for (side, triangles) in sides.iter_mut().zip(triangles.iter()) {
+        for triangle in triangles {
+            side.push(triangle.coordinates_triangle());
+        }
+    }
+    let [inside, outside] = sides;
+    CutResult { inside, outside }
+}
+
+fn total_area(triangles: &[StaticTriangle2d<StaticPoint2d>]) -> f64 {
+    // summing starts at -0.0, which would show up as the area of an empty side
+    triangles.iter().fold(0.0, |sum, t| sum + t.area().0.abs())
+}
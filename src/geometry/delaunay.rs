@@ -0,0 +1,325 @@
+//! Constrained Delaunay triangulation of a point set in plain floating point.
+//!
+//! The triangulation is built incrementally (Bowyer-Watson), constraint edges are
+//! then inserted by flipping the edges crossing them, and finally the remaining
+//! edges are flipped until the empty circumcircle property holds again.
+
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d};
+
+type Point = (f64, f64);
+
+/// Upper bound of edge flips per point, guards against cycling on degenerate input
+const FLIPS_PER_POINT: usize = 64;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Triangulation {
+    /// Counter-clockwise corners as indices into the input points
+    pub triangles: Vec<[usize; 3]>,
+    /// Constraint edges which are part of the triangulation
+    pub constrained: Vec<(usize, usize)>,
+    /// Constraint edges which could not be inserted, because they cross another
+    /// constraint or run through another point
+    pub rejected: Vec<(usize, usize)>,
+}
+
+impl Triangulation {
+    pub fn to_triangles(&self, points: &[StaticPoint2d]) -> Vec<StaticTriangle2d<StaticPoint2d>> {
+        self.triangles
+            .iter()
+            .map(|[a, b, c]| StaticTriangle2d::new(points[*a], points[*b], points[*c]))
+            .collect()
+    }
+    fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.triangles
+            .iter()
+            .any(|t| edges(t).contains(&(a, b)) || edges(t).contains(&(b, a)))
+    }
+}
+
+/// Center and radius of the circle through the three points, `None` if they are collinear
+pub fn circumcircle<P: Point2d>(a: &P, b: &P, c: &P) -> Option<(StaticPoint2d, f64)> {
+    let [(ax, ay), (bx, by), (cx, cy)] = [a, b, c].map(|p| coordinates(&p.coordinates()));
+    let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+    if d == 0.0 {
+        return None;
+    }
+    let (a2, b2, c2) = (ax * ax + ay * ay, bx * bx + by * by, cx * cx + cy * cy);
+    let x = (a2 * (by - cy) + b2 * (cy - ay) + c2 * (ay - by)) / d;
+    let y = (a2 * (cx - bx) + b2 * (ax - cx) + c2 * (bx - ax)) / d;
+    Some(((x, y).into(), (x - ax).hypot(y - ay)))
+}
+
+/// Triangulates the points, keeping the constraint edges given as pairs of point indices.
+///
+/// Points coinciding with an earlier point are left out.
+pub fn triangulate(points: &[StaticPoint2d], constraints: &[(usize, usize)]) -> Triangulation {
+    let mut coords: Vec<Point> = points.iter().map(coordinates).collect();
+    let n = coords.len();
+    let mut triangulation = Triangulation::default();
+    let Some(super_triangle) = super_triangle(&coords) else {
+        return triangulation;
+    };
+    coords.extend(super_triangle);
+    let mut triangles = vec![[n, n + 1, n + 2]];
+    for (idx, pt) in coords[..n].iter().enumerate() {
+        if coords[..idx].contains(pt) {
+            continue;
+        }
+        insert_point(&coords, &mut triangles, idx);
+    }
+    triangles.retain(|t| t.iter().all(|corner| *corner < n));
+    triangulation.triangles = triangles;
+
+    for (a, b) in constraints.iter().copied() {
+        if a == b || a >= n || b >= n {
+            continue;
+        }
+        if insert_constraint(&coords, &mut triangulation, a, b) {
+            triangulation.constrained.push((a, b));
+        } else {
+            triangulation.rejected.push((a, b));
+        }
+    }
+    restore_delaunay(&coords, &mut triangulation);
+    triangulation
+}
+
+fn coordinates<P: Point2d>(p: &P) -> Point {
+    let StaticPoint2d { x, y } = p.coordinates();
+    (x.0, y.0)
+}
+
+fn edges([a, b, c]: &[usize; 3]) -> [(usize, usize); 3] {
+    [(*a, *b), (*b, *c), (*c, *a)]
+}
+
+/// Twice the signed area, positive if `c` is left of the line `a -> b`
+fn orient((ax, ay): Point, (bx, by): Point, (cx, cy): Point) -> f64 {
+    (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+}
+
+/// Positive if `d` lies inside the circumcircle of the counter-clockwise triangle `a, b, c`
+fn in_circle(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let [(ax, ay), (bx, by), (cx, cy)] = [a, b, c].map(|(x, y)| (x - d.0, y - d.1));
+    (ax * ax + ay * ay) * (bx * cy - cx * by) - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay)
+}
+
+/// Counter-clockwise triangle far enough around all points that it doesn't change
+/// the triangulation of their convex hull in practice
+fn super_triangle(points: &[Point]) -> Option<[Point; 3]> {
+    let (first, rest) = points.split_first()?;
+    let (mut min, mut max) = (*first, *first);
+    for (x, y) in rest {
+        min = (min.0.min(*x), min.1.min(*y));
+        max = (max.0.max(*x), max.1.max(*y));
+    }
+    let (cx, cy) = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+    let size = (max.0 - min.0).max(max.1 - min.1).max(1.0) * 1000.0;
+    Some([
+        (cx - size, cy - size),
+        (cx + size, cy - size),
+        (cx, cy + size),
+    ])
+}
+
+fn insert_point(coords: &[Point], triangles: &mut Vec<[usize; 3]>, idx: usize) {
+    let pt = coords[idx];
+    let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles
+        .iter()
+        .partition(|[a, b, c]| in_circle(coords[*a], coords[*b], coords[*c], pt) > 0.0);
+    let bad_edges: Vec<(usize, usize)> = bad.iter().flat_map(edges).collect();
+    // the boundary of the cavity consists of the edges not shared by two bad triangles
+    let boundary = bad_edges
+        .iter()
+        .filter(|(a, b)| !bad_edges.contains(&(*b, *a)));
+    let mut next = good;
+    for (a, b) in boundary {
+        if orient(coords[*a], coords[*b], pt) > 0.0 {
+            next.push([*a, *b, idx]);
+        }
+    }
+    *triangles = next;
+}
+
+/// Finds the two triangles sharing the edge `a - b`, the first one containing it as `a -> b`
+fn edge_neighbours(triangles: &[[usize; 3]], a: usize, b: usize) -> Option<(usize, usize)> {
+    let first = triangles.iter().position(|t| edges(t).contains(&(a, b)))?;
+    let second = triangles.iter().position(|t| edges(t).contains(&(b, a)))?;
+    Some((first, second))
+}
+
+fn opposite(triangle: &[usize; 3], a: usize, b: usize) -> usize {
+    *triangle
+        .iter()
+        .find(|corner| **corner != a && **corner != b)
+        .expect("triangle with repeated corners")
+}
+
+/// Replaces the diagonal `a - b` of the quad formed by its two triangles by the other
+/// diagonal. Returns the new diagonal, or `None` if the quad is not strictly convex.
+fn flip(
+    coords: &[Point],
+    triangles: &mut [[usize; 3]],
+    a: usize,
+    b: usize,
+) -> Option<(usize, usize)> {
+    let (first, second) = edge_neighbours(triangles, a, b)?;
+    let left = opposite(&triangles[first], a, b);
+    let right = opposite(&triangles[second], a, b);
+    let side_a = orient(coords[right], coords[left], coords[a]);
+    let side_b = orient(coords[right], coords[left], coords[b]);
+    if side_a * side_b >= 0.0 {
+        return None;
+    }
+    triangles[first] = [left, a, right];
+    triangles[second] = [right, b, left];
+    Some((left, right))
+}
+
+/// Whether the segments `a - b` and `c - d` cross in a single point inside both of them
+fn crosses(coords: &[Point], (a, b): (usize, usize), (c, d): (usize, usize)) -> bool {
+    if a == c || a == d || b == c || b == d {
+        return false;
+    }
+    let [pa, pb, pc, pd] = [a, b, c, d].map(|idx| coords[idx]);
+    orient(pa, pb, pc) * orient(pa, pb, pd) < 0.0 && orient(pc, pd, pa) * orient(pc, pd, pb) < 0.0
+}
+
+/// Flips the edges crossing `a - b` until it is part of the triangulation
+fn insert_constraint(
+    coords: &[Point],
+    triangulation: &mut Triangulation,
+    a: usize,
+    b: usize,
+) -> bool {
+    let constrained = triangulation.constrained.clone();
+    let mut flips_left = FLIPS_PER_POINT * coords.len();
+    loop {
+        if triangulation.has_edge(a, b) {
+            return true;
+        }
+        let crossing: Vec<(usize, usize)> = triangulation
+            .triangles
+            .iter()
+            .flat_map(edges)
+            .filter(|(c, d)| c < d && crosses(coords, (a, b), (*c, *d)))
+            .collect();
+        if crossing.is_empty()
+            || crossing
+                .iter()
+                .any(|(c, d)| constrained.contains(&(*c, *d)) || constrained.contains(&(*d, *c)))
+        {
+            return false;
+        }
+        let mut flipped = false;
+        for (c, d) in crossing {
+            if flips_left == 0 {
+                return false;
+            }
+            if flip(coords, &mut triangulation.triangles, c, d).is_some() {
+                flips_left -= 1;
+                flipped = true;
+            }
+        }
+        if !flipped {
+            return false;
+        }
+    }
+}
+
+/// Flips edges violating the empty circumcircle property, except constrained ones
+fn restore_delaunay(coords: &[Point], triangulation: &mut Triangulation) {
+    let is_constrained = |(a, b): (usize, usize)| {
+        triangulation.constrained.contains(&(a, b)) || triangulation.constrained.contains(&(b, a))
+    };
+    let mut flips_left = FLIPS_PER_POINT * coords.len();
+    let mut changed = true;
+    while changed && flips_left > 0 {
+        changed = false;
+        let candidates: Vec<(usize, usize)> = triangulation
+            .triangles
+            .iter()
+            .flat_map(edges)
+            .filter(|edge| !is_constrained(*edge))
+            .collect();
+        for (a, b) in candidates {
+            let Some((first, second)) = edge_neighbours(&triangulation.triangles, a, b) else {
+                continue;
+            };
+            let [p, q, r] = triangulation.triangles[first].map(|idx| coords[idx]);
+            let far = coords[opposite(&triangulation.triangles[second], a, b)];
+            if in_circle(p, q, r, far) > 0.0
+                && flip(coords, &mut triangulation.triangles, a, b).is_some()
+            {
+                flips_left = flips_left.saturating_sub(1);
+                changed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticPoint2d;
+
+    use crate::geometry::delaunay::{circumcircle, triangulate};
+
+    fn points(coords: &[(f64, f64)]) -> Vec<StaticPoint2d> {
+        coords.iter().copied().map(StaticPoint2d::from).collect()
+    }
+
+    #[test]
+    fn test_circumcircle() {
+        let [a, b, c] = [(0.0, 0.0), (2.0, 0.0), (0.0, 2.0)].map(StaticPoint2d::from);
+        let (center, radius) = circumcircle(&a, &b, &c).unwrap();
+        assert_eq!(center, StaticPoint2d::from((1.0, 1.0)));
+        assert!((radius - 2.0f64.sqrt()).abs() < 1e-12);
+        assert_eq!(circumcircle(&a, &b, &StaticPoint2d::from((4.0, 0.0))), None);
+    }
+
+    #[test]
+    fn test_triangulate() {
+        // all four corners of a square are cocircular, either diagonal gives two triangles
+        let square = points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+        let t = triangulate(&square, &[]);
+        assert_eq!(t.triangles.len(), 2);
+
+        let mut cloud = square.clone();
+        cloud.extend(points(&[(5.0, 4.0), (2.0, 7.0), (8.0, 6.0), (5.0, 4.0)]));
+        let t = triangulate(&cloud, &[]);
+        // 4 hull points and 3 distinct interior points: 2 * 7 - 4 - 2 triangles
+        assert_eq!(t.triangles.len(), 8);
+        for [a, b, c] in &t.triangles {
+            assert!(![a, b, c].contains(&&7), "duplicate point used");
+            let circle = circumcircle(&cloud[*a], &cloud[*b], &cloud[*c]).unwrap();
+            for (idx, pt) in cloud.iter().enumerate() {
+                if ![*a, *b, *c].contains(&idx) {
+                    let (dx, dy) = (pt.x.0 - circle.0.x.0, pt.y.0 - circle.0.y.0);
+                    assert!(
+                        dx.hypot(dy) >= circle.1 - 1e-9,
+                        "point {idx} inside circumcircle"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_constraints() {
+        // the unconstrained triangulation uses the short diagonal 1 - 3
+        let kite = points(&[(0.0, 0.0), (5.0, -1.0), (10.0, 0.0), (5.0, 1.0)]);
+        let t = triangulate(&kite, &[]);
+        assert!(t.has_edge(1, 3));
+
+        let t = triangulate(&kite, &[(0, 2)]);
+        assert_eq!(t.constrained, vec![(0, 2)]);
+        assert!(t.has_edge(0, 2));
+        assert!(!t.has_edge(1, 3));
+        assert_eq!(t.triangles.len(), 2);
+
+        // the second constraint crosses the first one
+        let t = triangulate(&kite, &[(0, 2), (1, 3)]);
+        assert_eq!(t.rejected, vec![(1, 3)]);
+    }
+}
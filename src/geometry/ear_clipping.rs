@@ -0,0 +1,253 @@
+//! Triangulation of simple polygons with holes by clipping ears, recording every step.
+//!
+//! Holes are first merged into the outer ring by bridge edges to a visible outer
+//! vertex, which turns the polygon into a single weakly simple ring. Ears are then
+//! clipped from that ring in order.
+
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d};
+
+use crate::geometry::segments::segment_intersection;
+
+type Point = (f64, f64);
+
+/// One clipped ear
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Ear {
+    /// Corners as indices into [`EarClipping::vertices`]
+    pub corners: [usize; 3],
+    /// Ring left over after clipping this ear
+    pub remaining: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct EarClipping {
+    /// Outer ring with the holes spliced in, bridge vertices appear twice
+    #[serde(with = "crate::geometry::coordinates")]
+    pub vertices: Vec<StaticPoint2d>,
+    pub ears: Vec<Ear>,
+    /// `false` if clipping stopped early because no ear was found, which happens for
+    /// self-intersecting input
+    pub complete: bool,
+}
+
+impl EarClipping {
+    pub fn triangle(&self, ear: &Ear) -> StaticTriangle2d<StaticPoint2d> {
+        let [a, b, c] = ear.corners.map(|idx| self.vertices[idx]);
+        StaticTriangle2d::new(a, b, c)
+    }
+    pub fn triangles(&self) -> Vec<StaticTriangle2d<StaticPoint2d>> {
+        self.ears.iter().map(|ear| self.triangle(ear)).collect()
+    }
+    /// Sum of the absolute triangle areas
+    pub fn triangle_area(&self) -> f64 {
+        self.ears
+            .iter()
+            .map(|ear| {
+                let [a, b, c] = ear.corners.map(|idx| coordinates(&self.vertices[idx]));
+                orient(a, b, c).abs() / 2.0
+            })
+            .sum()
+    }
+}
+
+/// Area of the outer ring minus the area of the holes
+pub fn polygon_area(outer: &[StaticPoint2d], holes: &[Vec<StaticPoint2d>]) -> f64 {
+    let ring_area = |ring: &[StaticPoint2d]| signed_area(&to_coords(ring)).abs();
+    ring_area(outer) - holes.iter().map(|hole| ring_area(hole)).sum::<f64>()
+}
+
+pub fn triangulate(outer: &[StaticPoint2d], holes: &[Vec<StaticPoint2d>]) -> EarClipping {
+    let mut ring = to_coords(outer);
+    if signed_area(&ring) < 0.0 {
+        ring.reverse();
+    }
+    let mut holes: Vec<Vec<Point>> = holes
+        .iter()
+        .filter(|hole| hole.len() >= 3)
+        .map(|hole| {
+            let mut hole = to_coords(hole);
+            if signed_area(&hole) > 0.0 {
+                hole.reverse();
+            }
+            hole
+        })
+        .collect();
+    // bridging the rightmost holes first keeps the later bridges from crossing them
+    holes.sort_by(|h1, h2| max_x(h2).total_cmp(&max_x(h1)));
+    for idx in 0..holes.len() {
+        let (hole, others) = (&holes[idx], &holes[idx + 1..]);
+        if let Some(merged) = merge_hole(&ring, hole, others) {
+            ring = merged;
+        }
+    }
+    clip_ears(ring)
+}
+
+fn coordinates<P: Point2d>(p: &P) -> Point {
+    let StaticPoint2d { x, y } = p.coordinates();
+    (x.0, y.0)
+}
+
+fn to_coords(points: &[StaticPoint2d]) -> Vec<Point> {
+    points.iter().map(coordinates).collect()
+}
+
+fn max_x(ring: &[Point]) -> f64 {
+    ring.iter()
+        .map(|(x, _)| *x)
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Twice the signed area, positive if `c` is left of the line `a -> b`
+fn orient((ax, ay): Point, (bx, by): Point, (cx, cy): Point) -> f64 {
+    (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+}
+
+fn signed_area(ring: &[Point]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|idx| {
+            let ((x1, y1), (x2, y2)) = (ring[idx], ring[(idx + 1) % n]);
+            x1 * y2 - x2 * y1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+fn ring_edges(ring: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    (0..ring.len()).map(move |idx| (ring[idx], ring[(idx + 1) % ring.len()]))
+}
+
+/// Splices the hole into the ring through a bridge from its rightmost vertex to the
+/// nearest ring vertex it can see
+fn merge_hole(ring: &[Point], hole: &[Point], other_holes: &[Vec<Point>]) -> Option<Vec<Point>> {
+    let start = (0..hole.len()).max_by(|a, b| hole[*a].0.total_cmp(&hole[*b].0))?;
+    let from = hole[start];
+    let blocking: Vec<(Point, Point)> = ring_edges(ring)
+        .chain(ring_edges(hole))
+        .chain(other_holes.iter().flat_map(|other| ring_edges(other)))
+        .collect();
+    let visible = |to: Point| {
+        blocking.iter().all(|(p1, p2)| {
+            *p1 == to
+                || *p2 == to
+                || *p1 == from
+                || *p2 == from
+                || segment_intersection((from, to), (*p1, *p2)).is_none()
+        })
+    };
+    let distance = |(x, y): Point| (x - from.0).hypot(y - from.1);
+    let bridge = (0..ring.len())
+        .filter(|idx| visible(ring[*idx]))
+        .min_by(|a, b| distance(ring[*a]).total_cmp(&distance(ring[*b])))?;
+    let mut merged = ring[..=bridge].to_vec();
+    merged.extend((0..=hole.len()).map(|offset| hole[(start + offset) % hole.len()]));
+    merged.extend_from_slice(&ring[bridge..]);
+    Some(merged)
+}
+
+fn clip_ears(ring: Vec<Point>) -> EarClipping {
+    let mut remaining: Vec<usize> = (0..ring.len()).collect();
+    let mut ears = Vec::new();
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let corners_at = |idx: usize| {
+            [
+                remaining[(idx + n - 1) % n],
+                remaining[idx],
+                remaining[(idx + 1) % n],
+            ]
+        };
+        let Some(ear) = (0..n).find(|idx| is_ear(&ring, &remaining, corners_at(*idx))) else {
+            break;
+        };
+        let corners = corners_at(ear);
+        remaining.remove(ear);
+        ears.push(Ear {
+            corners,
+            remaining: remaining.clone(),
+        });
+    }
+    let complete = remaining.len() == 3;
+    if complete {
+        ears.push(Ear {
+            corners: [remaining[0], remaining[1], remaining[2]],
+            remaining: Vec::new(),
+        });
+    }
+    EarClipping {
+        vertices: ring.into_iter().map(StaticPoint2d::from).collect(),
+        ears,
+        complete,
+    }
+}
+
+/// Convex corner whose triangle contains no other vertex of the remaining ring
+fn is_ear(ring: &[Point], remaining: &[usize], [a, b, c]: [usize; 3]) -> bool {
+    let (pa, pb, pc) = (ring[a], ring[b], ring[c]);
+    if orient(pa, pb, pc) <= 0.0 {
+        return false;
+    }
+    remaining
+        .iter()
+        .map(|idx| ring[*idx])
+        // bridge vertices appear twice in the ring
+        .filter(|pt| *pt != pa && *pt != pb && *pt != pc)
+        .all(|pt| orient(pa, pb, pt) < 0.0 || orient(pb, pc, pt) < 0.0 || orient(pc, pa, pt) < 0.0)
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticPoint2d;
+
+    use crate::geometry::ear_clipping::{polygon_area, triangulate};
+
+    fn ring(coords: &[(f64, f64)]) -> Vec<StaticPoint2d> {
+        coords.iter().copied().map(StaticPoint2d::from).collect()
+    }
+
+    #[test]
+    fn test_concave_polygon() {
+        // clockwise L shape
+        let outer = ring(&[
+            (0.0, 0.0),
+            (0.0, 20.0),
+            (10.0, 20.0),
+            (10.0, 10.0),
+            (20.0, 10.0),
+            (20.0, 0.0),
+        ]);
+        let result = triangulate(&outer, &[]);
+        assert!(result.complete);
+        assert_eq!(result.ears.len(), 4);
+        assert_eq!(polygon_area(&outer, &[]), 300.0);
+        assert_eq!(result.triangle_area(), 300.0);
+        assert_eq!(result.ears.last().unwrap().remaining, Vec::<usize>::new());
+        assert_eq!(result.ears[0].remaining.len(), 5);
+    }
+
+    #[test]
+    fn test_polygon_with_holes() {
+        let outer = ring(&[(0.0, 0.0), (30.0, 0.0), (30.0, 20.0), (0.0, 20.0)]);
+        let holes = vec![
+            ring(&[(5.0, 5.0), (10.0, 5.0), (10.0, 15.0), (5.0, 15.0)]),
+            ring(&[(20.0, 5.0), (25.0, 5.0), (22.0, 15.0)]),
+        ];
+        let result = triangulate(&outer, &holes);
+        assert!(result.complete);
+        // n + 2h - 2 triangles for n vertices and h holes
+        assert_eq!(result.ears.len(), 11 + 2 * 2 - 2);
+        let expected = 600.0 - 50.0 - 25.0;
+        assert_eq!(polygon_area(&outer, &holes), expected);
+        assert!((result.triangle_area() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_self_intersecting() {
+        let bow_tie = ring(&[(0.0, 0.0), (10.0, 10.0), (10.0, 0.0), (0.0, 10.0)]);
+        let result = triangulate(&bow_tie, &[]);
+        let area_mismatch = (result.triangle_area() - polygon_area(&bow_tie, &[])).abs();
+        assert!(!result.complete || area_mismatch > 1.0);
+    }
+}
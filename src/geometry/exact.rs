@@ -0,0 +1,391 @@
+//! Comparison of a cut with an exact reference clip.
+//!
+//! `cut_to_triangles` works on [`Number`](triangles::prelude::Number), which is backed
+//! by `f64`, so its output carries rounding errors and it cannot be run exactly. Instead
+//! the triangle is clipped by the cutter independently, with Sutherland-Hodgman on
+//! arbitrary precision rationals. Rounding moves points by a few ulps, so larger
+//! differences from the reference hint at logic errors. This is a heuristic: pieces
+//! the reference does not know about are only caught by their corners, their side of
+//! the cutter and the areas.
+
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::geometry::cut::CutResult;
+
+type Exact = (BigRational, BigRational);
+
+/// Deviations below this fraction of the scene size count as rounding noise
+pub const RELATIVE_TOLERANCE: f64 = 1e-9;
+
+/// Intersection of the triangle and the cutter
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ExactIntersection {
+    /// Corners of the convex intersection polygon, rounded to the nearest `f64`
+    #[serde(with = "crate::geometry::coordinates")]
+    pub polygon: Vec<StaticPoint2d>,
+    /// Area rounded to the nearest `f64`
+    pub area: f64,
+}
+
+/// Differences between the floating point cut and the exact reference
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PrecisionReport {
+    pub exact: ExactIntersection,
+    /// Floating point inside area minus the exact one
+    pub inside_area_error: f64,
+    /// Floating point outside area minus the exact one
+    pub outside_area_error: f64,
+    /// Output corners which are not exactly one of the expected corners, with the
+    /// distance to the nearest one
+    #[serde(with = "crate::geometry::coordinates")]
+    pub moved_points: Vec<(StaticPoint2d, f64)>,
+    /// Expected corners no output triangle comes close to
+    #[serde(with = "crate::geometry::coordinates")]
+    pub missing_points: Vec<StaticPoint2d>,
+    /// Output triangles with exactly zero area
+    #[serde(with = "crate::geometry::coordinates")]
+    pub degenerate_triangles: Vec<StaticTriangle2d<StaticPoint2d>>,
+    /// Output triangles on the wrong side of the cutter
+    #[serde(with = "crate::geometry::coordinates")]
+    pub misclassified_triangles: Vec<StaticTriangle2d<StaticPoint2d>>,
+    /// Output triangles outside of the cut triangle or repeating an earlier one
+    #[serde(with = "crate::geometry::coordinates")]
+    pub appeared_triangles: Vec<StaticTriangle2d<StaticPoint2d>>,
+    /// Area of the reference inside and outside parts the output falls short of
+    pub disappeared_area: f64,
+    /// Distance up to which moved and missing points count as rounding noise
+    pub tolerance: f64,
+    /// Area up to which missing area counts as rounding noise
+    pub area_tolerance: f64,
+}
+
+impl PrecisionReport {
+    pub fn max_point_error(&self) -> f64 {
+        self.moved_points
+            .iter()
+            .map(|(_, d)| *d)
+            .fold(0.0, f64::max)
+    }
+    /// Whether the differences to the reference go beyond rounding noise
+    pub fn beyond_rounding(&self) -> bool {
+        !self.missing_points.is_empty()
+            || !self.misclassified_triangles.is_empty()
+            || !self.appeared_triangles.is_empty()
+            || self.disappeared_area > self.area_tolerance
+            || self.max_point_error() > self.tolerance
+    }
+}
+
+pub fn exact_intersection(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+) -> ExactIntersection {
+    let polygon = clip(&corners(triangle), &corners(cutter));
+    ExactIntersection {
+        polygon: polygon.iter().map(rounded).collect(),
+        area: to_f64(&polygon_area(&polygon)),
+    }
+}
+
+/// Compares a cut result of `triangle` and `cutter` with the reference clip
+pub fn compare(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+    result: &CutResult,
+) -> PrecisionReport {
+    let (triangle_corners, cutter_corners) = (corners(triangle), corners(cutter));
+    let intersection = clip(&triangle_corners, &cutter_corners);
+    let exact_inside = polygon_area(&intersection);
+    let exact_outside = polygon_area(&triangle_corners) - &exact_inside;
+
+    let scale = [triangle, cutter]
+        .into_iter()
+        .flat_map(|t| [*t.p1(), *t.p2(), *t.p3()])
+        .map(|p| {
+            let StaticPoint2d { x, y } = p.coordinates();
+            x.0.abs().max(y.0.abs())
+        })
+        .fold(0.0, f64::max);
+    let tolerance = scale.max(f64::MIN_POSITIVE) * RELATIVE_TOLERANCE;
+    let area_tolerance = scale * tolerance;
+
+    let expected: Vec<StaticPoint2d> = intersection
+        .iter()
+        .chain(triangle_corners.iter())
+        .map(rounded)
+        .collect();
+    let output: Vec<StaticPoint2d> = result
+        .inside
+        .iter()
+        .chain(result.outside.iter())
+        .flat_map(|t| [*t.p1(), *t.p2(), *t.p3()])
+        .collect();
+    let nearest = |pt: &StaticPoint2d, candidates: &[StaticPoint2d]| {
+        candidates
+            .iter()
+            .map(|c| distance(pt, c))
+            .fold(f64::INFINITY, f64::min)
+    };
+    let mut moved_points: Vec<(StaticPoint2d, f64)> = Vec::new();
+    for pt in &output {
+        let d = nearest(pt, &expected);
+        if d > 0.0 && !moved_points.iter().any(|(p, _)| p == pt) {
+            moved_points.push((*pt, d));
+        }
+    }
+    let mut missing_points: Vec<StaticPoint2d> = Vec::new();
+    for pt in &expected {
+        if nearest(pt, &output) > tolerance && !missing_points.contains(pt) {
+            missing_points.push(*pt);
+        }
+    }
+
+    let degenerate_triangles = result
+        .inside
+        .iter()
+        .chain(result.outside.iter())
+        .filter(|t| orient_triangle(&corners(t)).is_zero())
+        .copied()
+        .collect();
+    let covered = |t: &StaticTriangle2d<StaticPoint2d>, strict: bool| {
+        let centroid = centroid(&corners(t));
+        ccw_edges(&cutter_corners).iter().all(|(a, b)| {
+            let side = orient(a, b, &centroid);
+            if strict {
+                side.is_positive()
+            } else {
+                !side.is_negative()
+            }
+        })
+    };
+    let misclassified_triangles = result
+        .inside
+        .iter()
+        .filter(|t| !orient_triangle(&corners(t)).is_zero() && !covered(t, false))
+        .chain(result.outside.iter().filter(|t| covered(t, true)))
+        .copied()
+        .collect();
+    let within_triangle = |t: &StaticTriangle2d<StaticPoint2d>| {
+        let centroid = centroid(&corners(t));
+        ccw_edges(&triangle_corners)
+            .iter()
+            .all(|(a, b)| !orient(a, b, &centroid).is_negative())
+    };
+    let mut seen: Vec<[StaticPoint2d; 3]> = Vec::new();
+    let mut appeared_triangles = Vec::new();
+    for t in result.inside.iter().chain(result.outside.iter()) {
+        if orient_triangle(&corners(t)).is_zero() {
+            continue;
+        }
+        let mut key = [*t.p1(), *t.p2(), *t.p3()];
+        key.sort_by(|p, q| p.x.0.total_cmp(&q.x.0).then(p.y.0.total_cmp(&q.y.0)));
+        if seen.contains(&key) || !within_triangle(t) {
+            appeared_triangles.push(*t);
+        } else {
+            seen.push(key);
+        }
+    }
+    let inside_area_error = result.inside_area() - to_f64(&exact_inside);
+    let outside_area_error = result.outside_area() - to_f64(&exact_outside);
+
+    PrecisionReport {
+        exact: ExactIntersection {
+            polygon: intersection.iter().map(rounded).collect(),
+            area: to_f64(&exact_inside),
+        },
+        inside_area_error,
+        outside_area_error,
+        moved_points,
+        missing_points,
+        degenerate_triangles,
+        misclassified_triangles,
+        appeared_triangles,
+        disappeared_area: (-inside_area_error).max(0.0) + (-outside_area_error).max(0.0),
+        tolerance,
+        area_tolerance,
+    }
+}
+
+fn to_exact(value: f64) -> BigRational {
+    BigRational::from_float(value).expect("coordinates are finite")
+}
+
+fn to_f64(value: &BigRational) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+fn rounded((x, y): &Exact) -> StaticPoint2d {
+    (to_f64(x), to_f64(y)).into()
+}
+
+fn corners(triangle: &StaticTriangle2d<StaticPoint2d>) -> Vec<Exact> {
+    [triangle.p1(), triangle.p2(), triangle.p3()]
+        .into_iter()
+        .map(|p| {
+            let StaticPoint2d { x, y } = p.coordinates();
+            (to_exact(x.0), to_exact(y.0))
+        })
+        .collect()
+}
+
+fn distance(p1: &StaticPoint2d, p2: &StaticPoint2d) -> f64 {
+    (p1.x.0 - p2.x.0).hypot(p1.y.0 - p2.y.0)
+}
+
+/// Twice the signed area, positive if `c` is left of the line `a -> b`
+fn orient(a: &Exact, b: &Exact, c: &Exact) -> BigRational {
+    (&b.0 - &a.0) * (&c.1 - &a.1) - (&b.1 - &a.1) * (&c.0 - &a.0)
+}
+
+fn orient_triangle(corners: &[Exact]) -> BigRational {
+    orient(&corners[0], &corners[1], &corners[2])
+}
+
+fn centroid(corners: &[Exact]) -> Exact {
+    let three = BigRational::from_integer(3.into());
+    let (x, y) = corners.iter().fold(
+        (BigRational::zero(), BigRational::zero()),
+        |(x, y), (px, py)| (x + px, y + py),
+    );
+    (x / &three, y / three)
+}
+
+/// Edges of the cutter in counter-clockwise order
+fn ccw_edges(cutter: &[Exact]) -> Vec<(Exact, Exact)> {
+    let mut corners = cutter.to_vec();
+    if orient_triangle(&corners).is_negative() {
+        corners.reverse();
+    }
+    (0..corners.len())
+        .map(|idx| {
+            (
+                corners[idx].clone(),
+                corners[(idx + 1) % corners.len()].clone(),
+            )
+        })
+        .collect()
+}
+
+/// Sutherland-Hodgman clipping of a polygon by a convex polygon
+fn clip(subject: &[Exact], cutter: &[Exact]) -> Vec<Exact> {
+    if orient_triangle(cutter).is_zero() {
+        return Vec::new();
+    }
+    let mut polygon = subject.to_vec();
+    for (a, b) in ccw_edges(cutter) {
+        let mut clipped: Vec<Exact> = Vec::new();
+        for idx in 0..polygon.len() {
+            let (p, q) = (&polygon[idx], &polygon[(idx + 1) % polygon.len()]);
+            let (side_p, side_q) = (orient(&a, &b, p), orient(&a, &b, q));
+            if !side_p.is_negative() {
+                clipped.push(p.clone());
+            }
+            if (side_p.is_positive() && side_q.is_negative())
+                || (side_p.is_negative() && side_q.is_positive())
+            {
+                let t = &side_p / (&side_p - &side_q);
+                clipped.push((&p.0 + &t * (&q.0 - &p.0), &p.1 + &t * (&q.1 - &p.1)));
+            }
+        }
+        clipped.dedup();
+        if clipped.len() > 1 && clipped.first() == clipped.last() {
+            clipped.pop();
+        }
+        polygon = clipped;
+    }
+    polygon
+}
+
+fn polygon_area(polygon: &[Exact]) -> BigRational {
+    let n = polygon.len();
+    let twice = (0..n)
+        .map(|idx| {
+            let (p, q) = (&polygon[idx], &polygon[(idx + 1) % n]);
+            &p.0 * &q.1 - &q.0 * &p.1
+        })
+        .fold(BigRational::zero(), |acc, v| acc + v);
+    (twice / BigRational::from_integer(2.into())).abs()
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+    use crate::geometry::cut::CutResult;
+    use crate::geometry::exact::{compare, exact_intersection};
+
+    fn triangle(points: [(f64, f64); 3]) -> StaticTriangle2d<StaticPoint2d> {
+        let [p1, p2, p3] = points.map(StaticPoint2d::from);
+        StaticTriangle2d::new(p1, p2, p3)
+    }
+
+    #[test]
+    fn test_exact_intersection() {
+        let t = triangle([(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]);
+        let c = triangle([(0.0, 0.0), (0.0, 10.0), (10.0, 10.0)]);
+        let exact = exact_intersection(&t, &c);
+        assert_eq!(exact.area, 25.0);
+        assert_eq!(exact.polygon.len(), 3);
+        // coordinates like 0.1 are clipped with their exact binary values
+        let t = triangle([(0.0, 0.0), (0.3, 0.0), (0.0, 0.3)]);
+        let c = triangle([(0.1, 0.1), (0.1, -1.0), (1.0, 0.1)]);
+        assert!(exact_intersection(&t, &c).area > 0.0);
+        let far = triangle([(20.0, 20.0), (30.0, 20.0), (20.0, 30.0)]);
+        assert_eq!(exact_intersection(&t, &far).area, 0.0);
+    }
+
+    #[test]
+    fn test_compare() {
+        let t = triangle([(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]);
+        let c = triangle([(0.0, 0.0), (0.0, 10.0), (10.0, 10.0)]);
+        let inside = triangle([(0.0, 0.0), (5.0, 5.0), (0.0, 10.0)]);
+        let outside = triangle([(0.0, 0.0), (10.0, 0.0), (5.0, 5.0)]);
+        let good = CutResult {
+            inside: vec![inside],
+            outside: vec![outside],
+        };
+        let report = compare(&t, &c, &good);
+        assert_eq!(report.inside_area_error, 0.0);
+        assert!(report.moved_points.is_empty());
+        assert!(!report.beyond_rounding());
+
+        let nudged = triangle([(0.0, 0.0), (5.0 + 1e-14, 5.0), (0.0, 10.0)]);
+        let noisy = CutResult {
+            inside: vec![nudged],
+            outside: vec![outside],
+        };
+        let report = compare(&t, &c, &noisy);
+        assert_eq!(report.moved_points.len(), 1);
+        assert!(!report.beyond_rounding());
+
+        let swapped = CutResult {
+            inside: vec![outside],
+            outside: vec![inside],
+        };
+        let report = compare(&t, &c, &swapped);
+        assert_eq!(report.misclassified_triangles.len(), 2);
+        assert!(report.beyond_rounding());
+
+        // a piece outside of the triangle and a repeated one appear, a dropped one disappears
+        let stray = triangle([(20.0, 20.0), (30.0, 20.0), (20.0, 30.0)]);
+        let appeared = CutResult {
+            inside: vec![inside, inside],
+            outside: vec![outside, stray],
+        };
+        let report = compare(&t, &c, &appeared);
+        assert_eq!(report.appeared_triangles, vec![inside, stray]);
+        assert_eq!(report.disappeared_area, 0.0);
+        assert!(report.beyond_rounding());
+        let dropped = CutResult {
+            inside: vec![inside],
+            outside: Vec::new(),
+        };
+        let report = compare(&t, &c, &dropped);
+        assert!(report.appeared_triangles.is_empty());
+        assert_eq!(report.disappeared_area, 25.0);
+        assert!(report.beyond_rounding());
+    }
+}
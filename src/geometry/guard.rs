@@ -0,0 +1,112 @@
+//! Boundary around the `triangles` cut which turns panics and invalid input into errors.
+//!
+//! Catching only works where panics unwind. The web build aborts on panic, which is why
+//! the pages cut in the worker of [`crate::scene::compute`]. Where a cut still panics on
+//! the main thread, its input is available through [`running_cut`] so a panic hook can
+//! report it.
+
+use std::cell::RefCell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::geometry::cut::{cut_triangle, CutResult};
+
+type Triangle = StaticTriangle2d<StaticPoint2d>;
+
+thread_local! {
+    static RUNNING: RefCell<Option<(Triangle, Triangle)>> = const { RefCell::new(None) };
+}
+
+/// A cut which panicked or was refused
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CutFailure {
+    pub message: String,
+    #[serde(with = "crate::geometry::coordinates")]
+    pub triangle: Triangle,
+    #[serde(with = "crate::geometry::coordinates")]
+    pub cutter: Triangle,
+}
+
+impl CutFailure {
+    /// Corners of both triangles with all the digits needed to reproduce the failure
+    pub fn coordinates_text(&self) -> String {
+        format!(
+            "triangle {}, cutter {}",
+            corners_text(&self.triangle),
+            corners_text(&self.cutter)
+        )
+    }
+}
+
+/// Runs [`cut_triangle`], returning an error instead of panicking
+pub fn guarded_cut(triangle: &Triangle, cutter: &Triangle) -> Result<CutResult, CutFailure> {
+    let failure = |message: String| CutFailure {
+        message,
+        triangle: *triangle,
+        cutter: *cutter,
+    };
+    if !(is_finite(triangle) && is_finite(cutter)) {
+        return Err(failure("coordinates must be finite".to_string()));
+    }
+    RUNNING.with(|running| *running.borrow_mut() = Some((*triangle, *cutter)));
+    let result = catch_unwind(AssertUnwindSafe(|| cut_triangle(triangle, cutter)));
+    RUNNING.with(|running| *running.borrow_mut() = None);
+    result.map_err(|payload| {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload
+                .downcast_ref::<&str>()
+                .map_or("cut panicked", |message| message)
+                .to_string(),
+        };
+        failure(message)
+    })
+}
+
+/// Triangle and cutter of the [`guarded_cut`] currently running on this thread
+pub fn running_cut() -> Option<(Triangle, Triangle)> {
+    RUNNING.with(|running| running.try_borrow().ok().and_then(|running| *running))
+}
+
+fn coordinates(triangle: &Triangle) -> [(f64, f64); 3] {
+    [triangle.p1(), triangle.p2(), triangle.p3()].map(|p| {
+        let StaticPoint2d { x, y } = p.coordinates();
+        (x.0, y.0)
+    })
+}
+
+fn is_finite(triangle: &Triangle) -> bool {
+    coordinates(triangle)
+        .iter()
+        .all(|(x, y)| x.is_finite() && y.is_finite())
+}
+
+fn corners_text(triangle: &Triangle) -> String {
+    let [p1, p2, p3] = coordinates(triangle);
+    format!("{p1:?}, {p2:?}, {p3:?}")
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticTriangle2d;
+
+    use crate::geometry::guard::{guarded_cut, running_cut};
+
+    #[test]
+    fn test_guarded_cut() {
+        let triangle =
+            StaticTriangle2d::new((0.0, 0.0).into(), (10.0, 0.0).into(), (0.0, 10.0).into());
+        let invalid =
+            StaticTriangle2d::new((0.0, 0.0).into(), (f64::NAN, 0.0).into(), (0.0, 1.0).into());
+        assert!(guarded_cut(&triangle, &triangle).is_ok());
+        let failure = guarded_cut(&triangle, &invalid).unwrap_err();
+        assert_eq!(failure.message, "coordinates must be finite");
+        assert_eq!(
+            failure.coordinates_text(),
+            "triangle (0.0, 0.0), (10.0, 0.0), (0.0, 10.0), cutter (0.0, 0.0), (NaN, 0.0), (0.0, 1.0)"
+        );
+        assert_eq!(running_cut(), None);
+    }
+}
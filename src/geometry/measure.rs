@@ -0,0 +1,203 @@
+//! Distances, angles and triangle metrics shown by the measure tool
+
+use std::fmt;
+
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Distance {
+    pub distance: f64,
+    pub dx: f64,
+    pub dy: f64,
+}
+
+impl Distance {
+    pub fn between<P: Point2d>(from: &P, to: &P) -> Self {
+        let (x1, y1) = xy(from);
+        let (x2, y2) = xy(to);
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        Self {
+            distance: dx.hypot(dy),
+            dx,
+            dy,
+        }
+    }
+}
+
+/// Angle in degrees between 0 and 180 at `vertex` between the legs to `from` and `to`,
+/// `None` if one of the legs has no length
+pub fn angle_at<P: Point2d>(from: &P, vertex: &P, to: &P) -> Option<f64> {
+    let (x, y) = xy(vertex);
+    let (ax, ay) = xy(from);
+    let (bx, by) = xy(to);
+    let (ax, ay, bx, by) = (ax - x, ay - y, bx - x, by - y);
+    if (ax == 0.0 && ay == 0.0) || (bx == 0.0 && by == 0.0) {
+        return None;
+    }
+    // atan2 of cross and dot product stays accurate for angles near 0 and 180 degrees
+    Some(
+        (ax * by - ay * bx)
+            .abs()
+            .atan2(ax * bx + ay * by)
+            .to_degrees(),
+    )
+}
+
+/// Winding of the corners in world coordinates, where y points up
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    /// The corners are collinear
+    Degenerate,
+}
+
+impl Orientation {
+    pub fn of_signed_area(signed_area: f64) -> Self {
+        if signed_area > 0.0 {
+            Orientation::CounterClockwise
+        } else if signed_area < 0.0 {
+            Orientation::Clockwise
+        } else {
+            Orientation::Degenerate
+        }
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Orientation::CounterClockwise => "counter-clockwise",
+            Orientation::Clockwise => "clockwise",
+            Orientation::Degenerate => "degenerate",
+        })
+    }
+}
+
+/// Number of triangles winding each way
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct WindingCounts {
+    pub counter_clockwise: usize,
+    pub clockwise: usize,
+    pub degenerate: usize,
+}
+
+impl WindingCounts {
+    pub fn of<'a>(
+        triangles: impl IntoIterator<Item = &'a StaticTriangle2d<StaticPoint2d>>,
+    ) -> Self {
+        let mut counts = Self::default();
+        for triangle in triangles {
+            match TriangleMetrics::of(triangle).orientation {
+                Orientation::CounterClockwise => counts.counter_clockwise += 1,
+                Orientation::Clockwise => counts.clockwise += 1,
+                Orientation::Degenerate => counts.degenerate += 1,
+            }
+        }
+        counts
+    }
+
+    /// Whether all triangles with an area wind the same way
+    pub fn consistent(&self) -> bool {
+        self.counter_clockwise == 0 || self.clockwise == 0
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TriangleMetrics {
+    /// Positive for counter-clockwise corners
+    pub signed_area: f64,
+    pub perimeter: f64,
+    pub orientation: Orientation,
+}
+
+impl TriangleMetrics {
+    pub fn of(triangle: &StaticTriangle2d<StaticPoint2d>) -> Self {
+        let corners = [triangle.p1(), triangle.p2(), triangle.p3()];
+        let mut signed_area = 0.0;
+        let mut perimeter = 0.0;
+        for (idx, from) in corners.iter().enumerate() {
+            let to = corners[(idx + 1) % 3];
+            let (x1, y1) = xy(*from);
+            let (x2, y2) = xy(to);
+            signed_area += (x1 * y2 - x2 * y1) / 2.0;
+            perimeter += Distance::between(*from, to).distance;
+        }
+        Self {
+            signed_area,
+            perimeter,
+            orientation: Orientation::of_signed_area(signed_area),
+        }
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area.abs()
+    }
+}
+
+fn xy<P: Point2d>(p: &P) -> (f64, f64) {
+    let StaticPoint2d { x, y } = p.coordinates();
+    (x.0, y.0)
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+    use crate::geometry::measure::{
+        angle_at, Distance, Orientation, TriangleMetrics, WindingCounts,
+    };
+
+    fn pt(x: f64, y: f64) -> StaticPoint2d {
+        (x, y).into()
+    }
+
+    #[test]
+    fn test_distance_and_angle() {
+        let distance = Distance::between(&pt(1.0, 1.0), &pt(4.0, -3.0));
+        assert_eq!(
+            (distance.distance, distance.dx, distance.dy),
+            (5.0, 3.0, -4.0)
+        );
+
+        let right = angle_at(&pt(1.0, 0.0), &pt(0.0, 0.0), &pt(0.0, 2.0)).unwrap();
+        assert!((right - 90.0).abs() < 1e-12);
+        let straight = angle_at(&pt(-1.0, 0.0), &pt(0.0, 0.0), &pt(3.0, 0.0)).unwrap();
+        assert_eq!(straight, 180.0);
+        assert_eq!(angle_at(&pt(0.0, 0.0), &pt(0.0, 0.0), &pt(1.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_triangle_metrics() {
+        let ccw = StaticTriangle2d::new(pt(0.0, 0.0), pt(4.0, 0.0), pt(0.0, 3.0));
+        let metrics = TriangleMetrics::of(&ccw);
+        assert_eq!(metrics.signed_area, 6.0);
+        assert_eq!(metrics.perimeter, 12.0);
+        assert_eq!(metrics.orientation, Orientation::CounterClockwise);
+
+        let cw = StaticTriangle2d::new(pt(0.0, 0.0), pt(0.0, 3.0), pt(4.0, 0.0));
+        let metrics = TriangleMetrics::of(&cw);
+        assert_eq!((metrics.signed_area, metrics.area()), (-6.0, 6.0));
+        assert_eq!(metrics.orientation, Orientation::Clockwise);
+
+        let flat = StaticTriangle2d::new(pt(0.0, 0.0), pt(1.0, 1.0), pt(2.0, 2.0));
+        assert_eq!(
+            TriangleMetrics::of(&flat).orientation,
+            Orientation::Degenerate
+        );
+
+        let winding = WindingCounts::of([&ccw, &flat]);
+        assert!(winding.consistent());
+        let winding = WindingCounts::of([&ccw, &cw, &flat]);
+        assert_eq!(
+            (
+                winding.counter_clockwise,
+                winding.clockwise,
+                winding.degenerate
+            ),
+            (1, 1, 1)
+        );
+        assert!(!winding.consistent());
+        assert_eq!(Orientation::Clockwise.to_string(), "clockwise");
+    }
+}
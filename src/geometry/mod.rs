@@ -0,0 +1,16 @@
+pub mod containment;
+pub mod coordinates;
+pub mod cut;
+pub mod delaunay;
+pub mod ear_clipping;
+pub mod exact;
+pub mod guard;
+pub mod measure;
+pub mod presets;
+pub mod quality;
+pub mod robustness;
+pub mod segments;
+pub mod snap;
+#[cfg(test)]
+mod snapshot;
+pub mod transform;
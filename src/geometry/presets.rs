@@ -0,0 +1,156 @@
+use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+/// Expected outcome of cutting a preset scene
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ExpectedCut {
+    pub inside_triangles: usize,
+    pub outside_triangles: usize,
+    pub inside_area: f64,
+    pub outside_area: f64,
+}
+
+/// A named scene which is known to be hard for the cutting code
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Preset {
+    pub name: &'static str,
+    pub triangle: [(f64, f64); 3],
+    pub cutter: [(f64, f64); 3],
+    pub expected: ExpectedCut,
+}
+
+impl Preset {
+    pub fn triangle(&self) -> StaticTriangle2d<StaticPoint2d> {
+        to_triangle(&self.triangle)
+    }
+    pub fn cutter(&self) -> StaticTriangle2d<StaticPoint2d> {
+        to_triangle(&self.cutter)
+    }
+    pub fn find(name: &str) -> Option<&'static Preset> {
+        PRESETS.iter().find(|p| p.name == name)
+    }
+}
+
+fn to_triangle([p1, p2, p3]: &[(f64, f64); 3]) -> StaticTriangle2d<StaticPoint2d> {
+    StaticTriangle2d::new((*p1).into(), (*p2).into(), (*p3).into())
+}
+
+const BIG_TRIANGLE: [(f64, f64); 3] = [(-100.0, 0.0), (100.0, 0.0), (0.0, 100.0)];
+const BIG_AREA: f64 = 10000.0;
+
+const fn untouched(inside_triangles: usize, outside_triangles: usize) -> ExpectedCut {
+    ExpectedCut {
+        inside_triangles,
+        outside_triangles,
+        inside_area: 0.0,
+        outside_area: BIG_AREA,
+    }
+}
+
+pub const PRESETS: &[Preset] = &[
+    Preset {
+        name: "Default",
+        triangle: BIG_TRIANGLE,
+        cutter: [(-50.0, 25.0), (0.0, -25.0), (50.0, 25.0)],
+        expected: ExpectedCut {
+            inside_triangles: 2,
+            outside_triangles: 5,
+            inside_area: 1875.0,
+            outside_area: BIG_AREA - 1875.0,
+        },
+    },
+    Preset {
+        name: "Shared edge",
+        triangle: BIG_TRIANGLE,
+        cutter: [(-100.0, 0.0), (0.0, -100.0), (100.0, 0.0)],
+        expected: untouched(0, 1),
+    },
+    Preset {
+        name: "Shared vertex",
+        triangle: BIG_TRIANGLE,
+        cutter: [(0.0, 100.0), (50.0, 150.0), (-50.0, 150.0)],
+        expected: untouched(0, 1),
+    },
+    Preset {
+        name: "Cutter fully inside",
+        triangle: BIG_TRIANGLE,
+        cutter: [(-20.0, 20.0), (20.0, 20.0), (0.0, 50.0)],
+        expected: ExpectedCut {
+            inside_triangles: 1,
+            outside_triangles: 6,
+            inside_area: 600.0,
+            outside_area: BIG_AREA - 600.0,
+        },
+    },
+    Preset {
+        name: "Cutter fully outside",
+        triangle: BIG_TRIANGLE,
+        cutter: [(200.0, 0.0), (300.0, 0.0), (250.0, 100.0)],
+        expected: untouched(0, 1),
+    },
+    Preset {
+        name: "Triangle inside cutter",
+        triangle: BIG_TRIANGLE,
+        cutter: [(-200.0, -50.0), (200.0, -50.0), (0.0, 250.0)],
+        expected: ExpectedCut {
+            inside_triangles: 1,
+            outside_triangles: 0,
+            inside_area: BIG_AREA,
+            outside_area: 0.0,
+        },
+    },
+    Preset {
+        name: "Identical triangles",
+        triangle: BIG_TRIANGLE,
+        cutter: BIG_TRIANGLE,
+        expected: ExpectedCut {
+            inside_triangles: 1,
+            outside_triangles: 0,
+            inside_area: BIG_AREA,
+            outside_area: 0.0,
+        },
+    },
+    Preset {
+        name: "Touching at a single point",
+        triangle: BIG_TRIANGLE,
+        cutter: [(0.0, 0.0), (-50.0, -50.0), (50.0, -50.0)],
+        expected: untouched(0, 1),
+    },
+    Preset {
+        name: "Collinear overlapping edges",
+        triangle: BIG_TRIANGLE,
+        cutter: [(-50.0, 0.0), (50.0, -80.0), (150.0, 0.0)],
+        expected: untouched(0, 1),
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::cut::cut_triangle;
+    use crate::geometry::presets::PRESETS;
+
+    #[test]
+    fn test_presets() {
+        for preset in PRESETS {
+            let result = cut_triangle(&preset.triangle(), &preset.cutter());
+            let expected = preset.expected;
+            assert_eq!(
+                (result.inside.len(), result.outside.len()),
+                (expected.inside_triangles, expected.outside_triangles),
+                "triangle count of {}",
+                preset.name
+            );
+            assert!(
+                (result.inside_area() - expected.inside_area).abs() < 1e-6,
+                "inside area of {}: {}",
+                preset.name,
+                result.inside_area()
+            );
+            assert!(
+                (result.outside_area() - expected.outside_area).abs() < 1e-6,
+                "outside area of {}: {}",
+                preset.name,
+                result.outside_area()
+            );
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Shape quality measures of single triangles
+
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+/// Minimum angle in degrees from which a triangle counts as [`Grade::Good`]
+pub const GOOD_MIN_ANGLE: f64 = 30.0;
+/// Minimum angle in degrees from which a triangle counts as [`Grade::Fair`]
+pub const FAIR_MIN_ANGLE: f64 = 15.0;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TriangleQuality {
+    /// Smallest interior angle in degrees
+    pub min_angle: f64,
+    /// Largest interior angle in degrees
+    pub max_angle: f64,
+    /// Circumradius divided by twice the inradius, 1 for equilateral triangles and
+    /// infinite for degenerate ones
+    pub radius_ratio: f64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Grade {
+    Good,
+    Fair,
+    Poor,
+}
+
+impl TriangleQuality {
+    pub fn of(triangle: &StaticTriangle2d<StaticPoint2d>) -> Self {
+        let [a, b, c] = [triangle.p1(), triangle.p2(), triangle.p3()].map(|p| {
+            let StaticPoint2d { x, y } = p.coordinates();
+            (x.0, y.0)
+        });
+        let length = |(x1, y1): (f64, f64), (x2, y2): (f64, f64)| (x2 - x1).hypot(y2 - y1);
+        // side lengths opposite of the corners a, b and c
+        let sides = [length(b, c), length(c, a), length(a, b)];
+        let [la, lb, lc] = sides;
+        if sides.contains(&0.0) {
+            return Self::degenerate();
+        }
+        let angle = |opposite: f64, s1: f64, s2: f64| {
+            ((s1 * s1 + s2 * s2 - opposite * opposite) / (2.0 * s1 * s2))
+                .clamp(-1.0, 1.0)
+                .acos()
+                .to_degrees()
+        };
+        let angles = [angle(la, lb, lc), angle(lb, lc, la), angle(lc, la, lb)];
+        let area = triangle.area().0.abs();
+        let semi_perimeter = (la + lb + lc) / 2.0;
+        let radius_ratio = if area == 0.0 {
+            f64::INFINITY
+        } else {
+            let circumradius = la * lb * lc / (4.0 * area);
+            let inradius = area / semi_perimeter;
+            circumradius / (2.0 * inradius)
+        };
+        Self {
+            min_angle: angles.into_iter().fold(f64::INFINITY, f64::min),
+            max_angle: angles.into_iter().fold(0.0, f64::max),
+            radius_ratio,
+        }
+    }
+
+    fn degenerate() -> Self {
+        Self {
+            min_angle: 0.0,
+            max_angle: 180.0,
+            radius_ratio: f64::INFINITY,
+        }
+    }
+
+    pub fn grade(&self) -> Grade {
+        if self.min_angle >= GOOD_MIN_ANGLE {
+            Grade::Good
+        } else if self.min_angle >= FAIR_MIN_ANGLE {
+            Grade::Fair
+        } else {
+            Grade::Poor
+        }
+    }
+}
+
+/// Aggregated quality of a set of triangles
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MeshQuality {
+    pub triangles: usize,
+    pub min_angle: f64,
+    pub max_angle: f64,
+    pub mean_min_angle: f64,
+    pub max_radius_ratio: f64,
+    pub poor_triangles: usize,
+}
+
+impl MeshQuality {
+    /// Returns `None` for an empty set of triangles
+    pub fn of<'a>(
+        triangles: impl IntoIterator<Item = &'a StaticTriangle2d<StaticPoint2d>>,
+    ) -> Option<Self> {
+        let qualities: Vec<TriangleQuality> =
+            triangles.into_iter().map(TriangleQuality::of).collect();
+        if qualities.is_empty() {
+            return None;
+        }
+        Some(Self {
+            triangles: qualities.len(),
+            min_angle: qualities
+                .iter()
+                .map(|q| q.min_angle)
+                .fold(f64::INFINITY, f64::min),
+            max_angle: qualities.iter().map(|q| q.max_angle).fold(0.0, f64::max),
+            mean_min_angle: qualities.iter().map(|q| q.min_angle).sum::<f64>()
+                / qualities.len() as f64,
+            max_radius_ratio: qualities.iter().map(|q| q.radius_ratio).fold(0.0, f64::max),
+            poor_triangles: qualities
+                .iter()
+                .filter(|q| q.grade() == Grade::Poor)
+                .count(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+    use crate::geometry::quality::{Grade, TriangleQuality};
+
+    fn triangle(points: [(f64, f64); 3]) -> StaticTriangle2d<StaticPoint2d> {
+        let [p1, p2, p3] = points.map(StaticPoint2d::from);
+        StaticTriangle2d::new(p1, p2, p3)
+    }
+
+    #[test]
+    fn test_triangle_quality() {
+        let equilateral =
+            TriangleQuality::of(&triangle([(0.0, 0.0), (2.0, 0.0), (1.0, 3.0f64.sqrt())]));
+        assert!((equilateral.min_angle - 60.0).abs() < 1e-9);
+        assert!((equilateral.max_angle - 60.0).abs() < 1e-9);
+        assert!((equilateral.radius_ratio - 1.0).abs() < 1e-9);
+        assert_eq!(equilateral.grade(), Grade::Good);
+
+        let right = TriangleQuality::of(&triangle([(0.0, 0.0), (0.0, 1.0), (1.0, 0.0)]));
+        assert!((right.min_angle - 45.0).abs() < 1e-9);
+        assert!((right.max_angle - 90.0).abs() < 1e-9);
+
+        let sliver = TriangleQuality::of(&triangle([(0.0, 0.0), (10.0, 0.0), (5.0, 0.1)]));
+        assert_eq!(sliver.grade(), Grade::Poor);
+        assert!(sliver.max_angle > 170.0);
+
+        let degenerate = TriangleQuality::of(&triangle([(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)]));
+        assert_eq!(degenerate.min_angle, 0.0);
+        assert_eq!(degenerate.radius_ratio, f64::INFINITY);
+    }
+}
@@ -0,0 +1,122 @@
+//! Invariant checks of a single cut, used to sweep a corner over the configuration space.
+//!
+//! Panics are caught by [`guarded_cut`] where they unwind. The web build aborts on
+//! panic, there the sweep runs in the worker of [`crate::scene::compute`], which answers
+//! cell by cell, and a cell which ends the worker is recorded as panicked.
+
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::geometry::cut::CutResult;
+use crate::geometry::exact::{exact_intersection, RELATIVE_TOLERANCE};
+use crate::geometry::guard::guarded_cut;
+
+/// Invariants a cut result broke
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct Violations {
+    /// `cut_to_triangles` panicked or refused the input, no result to check
+    pub panicked: bool,
+    /// The pieces do not add up to the triangle, or the inside pieces differ from the
+    /// exact intersection
+    pub area_mismatch: bool,
+    /// Some piece has zero area
+    pub degenerate_output: bool,
+}
+
+impl Violations {
+    pub fn any(&self) -> bool {
+        self.panicked || self.area_mismatch || self.degenerate_output
+    }
+    /// Heat of the worst violation, between 0 for none and 1 for a panic
+    pub fn severity(&self) -> f64 {
+        if self.panicked {
+            1.0
+        } else if self.area_mismatch {
+            0.7
+        } else if self.degenerate_output {
+            0.4
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Cuts `triangle` with `cutter` and checks the result
+pub fn check_cut(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+) -> Violations {
+    match guarded_cut(triangle, cutter) {
+        Ok(result) => check_result(triangle, cutter, &result),
+        Err(_) => Violations {
+            panicked: true,
+            ..Violations::default()
+        },
+    }
+}
+
+/// Checks a cut result of `triangle` and `cutter` against the areas it has to preserve
+pub fn check_result(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+    result: &CutResult,
+) -> Violations {
+    let triangle_area = triangle.area().0.abs();
+    let tolerance = triangle_area.max(f64::MIN_POSITIVE) * RELATIVE_TOLERANCE;
+    let inside_area = result.inside_area();
+    let total_area = inside_area + result.outside_area();
+    let exact_area = exact_intersection(triangle, cutter).area;
+    Violations {
+        panicked: false,
+        area_mismatch: (total_area - triangle_area).abs() > tolerance
+            || (inside_area - exact_area).abs() > tolerance,
+        degenerate_output: result
+            .inside
+            .iter()
+            .chain(result.outside.iter())
+            .any(|t| t.area().0 == 0.0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+    use crate::geometry::cut::CutResult;
+    use crate::geometry::robustness::{check_result, Violations};
+
+    fn triangle(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> StaticTriangle2d<StaticPoint2d> {
+        StaticTriangle2d::new(a.into(), b.into(), c.into())
+    }
+
+    #[test]
+    fn test_check_result() {
+        let cut = triangle((0.0, 0.0), (10.0, 0.0), (0.0, 10.0));
+        // covers everything left of x = 5
+        let cutter = triangle((-5.0, -5.0), (5.0, -5.0), (5.0, 25.0));
+        let valid = CutResult {
+            inside: vec![
+                triangle((0.0, 0.0), (5.0, 0.0), (5.0, 5.0)),
+                triangle((0.0, 0.0), (5.0, 5.0), (0.0, 10.0)),
+            ],
+            outside: vec![triangle((5.0, 0.0), (10.0, 0.0), (5.0, 5.0))],
+        };
+        assert_eq!(check_result(&cut, &cutter, &valid), Violations::default());
+
+        let swapped = CutResult {
+            inside: valid.outside.clone(),
+            outside: valid.inside.clone(),
+        };
+        let violations = check_result(&cut, &cutter, &swapped);
+        assert!(violations.area_mismatch);
+        assert_eq!(violations.severity(), 0.7);
+
+        let mut degenerate = valid.clone();
+        degenerate
+            .outside
+            .push(triangle((0.0, 0.0), (5.0, 5.0), (10.0, 10.0)));
+        let violations = check_result(&cut, &cutter, &degenerate);
+        assert!(violations.degenerate_output && !violations.area_mismatch);
+        assert!(violations.any());
+    }
+}
@@ -0,0 +1,31 @@
+//! Plain floating point helpers on segments given as pairs of `(x, y)` coordinates
+
+pub type Segment = ((f64, f64), (f64, f64));
+
+pub fn closest_point_on_segment(((x1, y1), (x2, y2)): Segment, (x, y): (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let length_square = dx * dx + dy * dy;
+    if length_square == 0.0 {
+        return (x1, y1);
+    }
+    let t = (((x - x1) * dx + (y - y1) * dy) / length_square).clamp(0.0, 1.0);
+    (x1 + t * dx, y1 + t * dy)
+}
+
+/// Intersection point of two segments, `None` for disjoint or parallel segments
+pub fn segment_intersection(
+    ((x1, y1), (x2, y2)): Segment,
+    ((x3, y3), (x4, y4)): Segment,
+) -> Option<(f64, f64)> {
+    let denominator = (x2 - x1) * (y4 - y3) - (y2 - y1) * (x4 - x3);
+    if denominator == 0.0 {
+        return None;
+    }
+    let t = ((x3 - x1) * (y4 - y3) - (y3 - y1) * (x4 - x3)) / denominator;
+    let u = ((x3 - x1) * (y2 - y1) - (y3 - y1) * (x2 - x1)) / denominator;
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+        Some((x1 + t * (x2 - x1), y1 + t * (y2 - y1)))
+    } else {
+        None
+    }
+}
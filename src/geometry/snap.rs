@@ -0,0 +1,204 @@
+use triangles::prelude::{Point2d, StaticPoint2d};
+
+use crate::geometry::segments::{closest_point_on_segment, segment_intersection};
+
+/// Kind of position a dragged point snapped to, ordered by precedence
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SnapKind {
+    Vertex,
+    Intersection,
+    Midpoint,
+    Edge,
+    Integer,
+    Grid,
+}
+
+/// Enabled snap targets
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct SnapModes {
+    pub grid: bool,
+    pub integer: bool,
+    pub vertices: bool,
+    pub edges: bool,
+    pub midpoints: bool,
+    pub intersections: bool,
+}
+
+impl Default for SnapModes {
+    fn default() -> Self {
+        Self {
+            grid: true,
+            integer: false,
+            vertices: true,
+            edges: true,
+            midpoints: true,
+            intersections: true,
+        }
+    }
+}
+
+impl SnapModes {
+    fn is_enabled(&self, kind: SnapKind) -> bool {
+        match kind {
+            SnapKind::Vertex => self.vertices,
+            SnapKind::Intersection => self.intersections,
+            SnapKind::Midpoint => self.midpoints,
+            SnapKind::Edge => self.edges,
+            SnapKind::Integer => self.integer,
+            SnapKind::Grid => self.grid,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SnapTarget {
+    pub point: StaticPoint2d,
+    pub kind: SnapKind,
+}
+
+/// Finds the position a point snaps to, given the surrounding geometry.
+///
+/// Targets are checked in the order of [`SnapKind`], within one kind the nearest
+/// target inside the tolerance wins.
+pub struct Snapper {
+    modes: SnapModes,
+    tolerance: f64,
+    grid_step: (f64, f64),
+    vertices: Vec<(f64, f64)>,
+    edges: Vec<((f64, f64), (f64, f64))>,
+}
+
+impl Snapper {
+    pub fn new(modes: SnapModes, tolerance: f64, grid_step: (f64, f64)) -> Self {
+        Self {
+            modes,
+            tolerance,
+            grid_step,
+            vertices: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+    pub fn with_vertices<'a, P: Point2d + 'a>(
+        mut self,
+        points: impl IntoIterator<Item = &'a P>,
+    ) -> Self {
+        self.vertices
+            .extend(points.into_iter().map(|p| coordinates(&p.coordinates())));
+        self
+    }
+    pub fn with_edges<'a, P: Point2d + 'a>(
+        mut self,
+        edges: impl IntoIterator<Item = (&'a P, &'a P)>,
+    ) -> Self {
+        self.edges.extend(edges.into_iter().map(|(p1, p2)| {
+            (
+                coordinates(&p1.coordinates()),
+                coordinates(&p2.coordinates()),
+            )
+        }));
+        self
+    }
+
+    pub fn snap<P: Point2d>(&self, point: &P) -> Option<SnapTarget> {
+        let pt = coordinates(&point.coordinates());
+        [
+            SnapKind::Vertex,
+            SnapKind::Intersection,
+            SnapKind::Midpoint,
+            SnapKind::Edge,
+            SnapKind::Integer,
+            SnapKind::Grid,
+        ]
+        .into_iter()
+        .filter(|kind| self.modes.is_enabled(*kind))
+        .find_map(|kind| {
+            self.candidates(kind, pt)
+                .into_iter()
+                .map(|candidate| (dist(candidate, pt), candidate))
+                .filter(|(d, _)| *d <= self.tolerance)
+                .min_by(|(d1, _), (d2, _)| d1.total_cmp(d2))
+                .map(|(_, (x, y))| SnapTarget {
+                    point: (x, y).into(),
+                    kind,
+                })
+        })
+    }
+
+    fn candidates(&self, kind: SnapKind, (x, y): (f64, f64)) -> Vec<(f64, f64)> {
+        match kind {
+            SnapKind::Vertex => self.vertices.clone(),
+            SnapKind::Intersection => {
+                let mut found = Vec::new();
+                for (idx, e1) in self.edges.iter().enumerate() {
+                    for e2 in self.edges[idx + 1..].iter() {
+                        if let Some(pt) = segment_intersection(*e1, *e2) {
+                            found.push(pt);
+                        }
+                    }
+                }
+                found
+            }
+            SnapKind::Midpoint => self
+                .edges
+                .iter()
+                .map(|((x1, y1), (x2, y2))| ((x1 + x2) / 2.0, (y1 + y2) / 2.0))
+                .collect(),
+            SnapKind::Edge => self
+                .edges
+                .iter()
+                .map(|edge| closest_point_on_segment(*edge, (x, y)))
+                .collect(),
+            SnapKind::Integer => vec![(x.round(), y.round())],
+            SnapKind::Grid => {
+                let (step_x, step_y) = self.grid_step;
+                if step_x > 0.0 && step_y > 0.0 {
+                    vec![((x / step_x).round() * step_x, (y / step_y).round() * step_y)]
+                } else {
+                    vec![]
+                }
+            }
+        }
+    }
+}
+
+fn coordinates(p: &StaticPoint2d) -> (f64, f64) {
+    (p.x.0, p.y.0)
+}
+
+fn dist((x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> f64 {
+    (x2 - x1).hypot(y2 - y1)
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticPoint2d;
+
+    use crate::geometry::snap::{SnapKind, SnapModes, Snapper};
+
+    #[test]
+    fn test_snap_precedence() {
+        let p1: StaticPoint2d = (0.0, 0.0).into();
+        let p2: StaticPoint2d = (10.0, 0.0).into();
+        let p3: StaticPoint2d = (5.0, -5.0).into();
+        let p4: StaticPoint2d = (5.0, 5.0).into();
+        let snapper = Snapper::new(SnapModes::default(), 1.0, (2.0, 2.0))
+            .with_vertices([&p1, &p2])
+            .with_edges([(&p1, &p2), (&p3, &p4)]);
+
+        let target = snapper.snap(&StaticPoint2d::from((0.5, 0.5))).unwrap();
+        assert_eq!(target.kind, SnapKind::Vertex);
+        assert_eq!(target.point, p1);
+
+        let target = snapper.snap(&StaticPoint2d::from((5.3, 0.4))).unwrap();
+        assert_eq!(target.kind, SnapKind::Intersection);
+        assert_eq!(target.point, (5.0, 0.0).into());
+
+        let target = snapper.snap(&StaticPoint2d::from((3.0, 0.7))).unwrap();
+        assert_eq!(target.kind, SnapKind::Edge);
+        assert_eq!(target.point, (3.0, 0.0).into());
+
+        let target = snapper.snap(&StaticPoint2d::from((1.9, 4.2))).unwrap();
+        assert_eq!(target.kind, SnapKind::Grid);
+        assert_eq!(target.point, (2.0, 4.0).into());
+    }
+}
@@ -0,0 +1,110 @@
+//! Stable textual representation of cut results.
+//!
+//! The regression test compares the cut of every scene with its file in `snapshots/cut`.
+//! Missing or differing results are written next to them as `.snap.new` and fail the
+//! test. Set `UPDATE_SNAPSHOTS=1` to write the `.snap` files instead. The module is only
+//! built for the tests.
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use triangles::prelude::{Point2d, Polygon2d, StaticPoint2d, StaticTriangle2d};
+
+use crate::geometry::cut::{cut_triangle, CutResult};
+use crate::geometry::presets::PRESETS;
+
+fn format_cut(
+    name: &str,
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+    result: &CutResult,
+) -> String {
+    let mut out = String::new();
+    writeln!(out, "scene: {name}").unwrap();
+    writeln!(out, "triangle: {}", format_triangle(triangle)).unwrap();
+    writeln!(out, "cutter: {}", format_triangle(cutter)).unwrap();
+    for (label, triangles, area) in [
+        ("inside", &result.inside, result.inside_area()),
+        ("outside", &result.outside, result.outside_area()),
+    ] {
+        writeln!(out, "{label}: {} triangles, area {area}", triangles.len()).unwrap();
+        for triangle in triangles {
+            writeln!(out, "  {}", format_triangle(triangle)).unwrap();
+        }
+    }
+    out
+}
+
+fn format_triangle(triangle: &StaticTriangle2d<StaticPoint2d>) -> String {
+    triangle
+        .points()
+        .map(|p| {
+            let StaticPoint2d { x, y } = p.coordinates();
+            format!("({}, {})", x.0, y.0)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+type Scene = (String, [(f64, f64); 3], [(f64, f64); 3]);
+
+/// The presets, plus rotated, tiny, huge and sliver variants of them
+fn corpus() -> Vec<Scene> {
+    let mut scenes = Vec::new();
+    for preset in PRESETS {
+        let name = preset.name.to_lowercase().replace(' ', "_");
+        scenes.push((name.clone(), preset.triangle, preset.cutter));
+        let rotate = |[a, b, c]: [(f64, f64); 3]| [(-a.1, a.0), (-b.1, b.0), (-c.1, c.0)];
+        scenes.push((
+            format!("{name}_rotated"),
+            rotate(preset.triangle),
+            rotate(preset.cutter),
+        ));
+        for (suffix, factor) in [("tiny", 1e-6), ("huge", 1e6)] {
+            let scale = |pts: [(f64, f64); 3]| pts.map(|(x, y)| (x * factor, y * factor));
+            scenes.push((
+                format!("{name}_{suffix}"),
+                scale(preset.triangle),
+                scale(preset.cutter),
+            ));
+        }
+    }
+    scenes.push((
+        "sliver_cutter".to_string(),
+        [(-100.0, 0.0), (100.0, 0.0), (0.0, 100.0)],
+        [(-150.0, 50.0), (150.0, 50.0 + 1e-9), (0.0, 50.0 - 1e-9)],
+    ));
+    scenes.push((
+        "sliver_triangle".to_string(),
+        [(-100.0, 0.0), (100.0, 1e-7), (0.0, -1e-7)],
+        [(-50.0, -50.0), (50.0, -50.0), (0.0, 50.0)],
+    ));
+    scenes
+}
+
+#[test]
+fn test_cut_snapshots() {
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("snapshots/cut");
+    std::fs::create_dir_all(&directory).unwrap();
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut failed = Vec::new();
+    for (name, triangle, cutter) in corpus() {
+        let to_triangle = |[p1, p2, p3]: [(f64, f64); 3]| {
+            triangles::prelude::StaticTriangle2d::new(p1.into(), p2.into(), p3.into())
+        };
+        let (triangle, cutter) = (to_triangle(triangle), to_triangle(cutter));
+        let actual = format_cut(&name, &triangle, &cutter, &cut_triangle(&triangle, &cutter));
+        let path = directory.join(format!("{name}.snap"));
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            _ if update => std::fs::write(&path, actual).unwrap(),
+            _ => {
+                std::fs::write(path.with_extension("snap.new"), actual).unwrap();
+                failed.push(name);
+            }
+        }
+    }
+    assert!(
+        failed.is_empty(),
+        "snapshots missing or different: {failed:?}, rerun with UPDATE_SNAPSHOTS=1 to accept"
+    );
+}
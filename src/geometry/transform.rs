@@ -0,0 +1,204 @@
+//! Affine transformations of points and triangles
+
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+/// `x' = a x + b y + tx`, `y' = c x + d y + ty`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Affine {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub tx: f64,
+    pub ty: f64,
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Affine {
+    pub const IDENTITY: Affine = Affine {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        tx: 0.0,
+        ty: 0.0,
+    };
+
+    pub fn translation(dx: f64, dy: f64) -> Self {
+        Self {
+            tx: dx,
+            ty: dy,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Counter-clockwise rotation by `degrees` around `center`
+    pub fn rotation<P: Point2d>(center: &P, degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self {
+            a: cos,
+            b: -sin,
+            c: sin,
+            d: cos,
+            ..Self::IDENTITY
+        }
+        .around(center)
+    }
+
+    /// Uniform scaling by `factor` away from `center`
+    pub fn scaling<P: Point2d>(center: &P, factor: f64) -> Self {
+        Self {
+            a: factor,
+            d: factor,
+            ..Self::IDENTITY
+        }
+        .around(center)
+    }
+
+    /// Mirroring at the vertical line through `center`
+    pub fn mirror_x<P: Point2d>(center: &P) -> Self {
+        Self {
+            a: -1.0,
+            ..Self::IDENTITY
+        }
+        .around(center)
+    }
+
+    /// The linear part of `self` applied with `center` as fixed point
+    fn around<P: Point2d>(self, center: &P) -> Self {
+        let StaticPoint2d { x, y } = center.coordinates();
+        Affine::translation(x.0, y.0)
+            .then_after(&self)
+            .then_after(&Affine::translation(-x.0, -y.0))
+    }
+
+    /// `self` applied to the result of `first`
+    pub fn then_after(&self, first: &Affine) -> Self {
+        Self {
+            a: self.a * first.a + self.b * first.c,
+            b: self.a * first.b + self.b * first.d,
+            c: self.c * first.a + self.d * first.c,
+            d: self.c * first.b + self.d * first.d,
+            tx: self.a * first.tx + self.b * first.ty + self.tx,
+            ty: self.c * first.tx + self.d * first.ty + self.ty,
+        }
+    }
+
+    /// The transformation undoing `self`, `None` if it collapses the plane
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+        let (a, b, c, d) = (
+            self.d / determinant,
+            -self.b / determinant,
+            -self.c / determinant,
+            self.a / determinant,
+        );
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            tx: -(a * self.tx + b * self.ty),
+            ty: -(c * self.tx + d * self.ty),
+        })
+    }
+
+    /// Negative for transformations which flip the orientation
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    pub fn apply<P: Point2d>(&self, point: &P) -> StaticPoint2d {
+        let StaticPoint2d { x, y } = point.coordinates();
+        (
+            self.a * x.0 + self.b * y.0 + self.tx,
+            self.c * x.0 + self.d * y.0 + self.ty,
+        )
+            .into()
+    }
+
+    pub fn apply_triangle(
+        &self,
+        triangle: &StaticTriangle2d<StaticPoint2d>,
+    ) -> StaticTriangle2d<StaticPoint2d> {
+        StaticTriangle2d::new(
+            self.apply(triangle.p1()),
+            self.apply(triangle.p2()),
+            self.apply(triangle.p3()),
+        )
+    }
+}
+
+/// Center of mass of the corners
+pub fn centroid(triangle: &StaticTriangle2d<StaticPoint2d>) -> StaticPoint2d {
+    let (mut x, mut y) = (0.0, 0.0);
+    for pt in [triangle.p1(), triangle.p2(), triangle.p3()] {
+        let StaticPoint2d { x: px, y: py } = pt.coordinates();
+        x += px.0 / 3.0;
+        y += py.0 / 3.0;
+    }
+    (x, y).into()
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+    use crate::geometry::transform::{centroid, Affine};
+
+    fn pt(x: f64, y: f64) -> StaticPoint2d {
+        (x, y).into()
+    }
+
+    fn assert_near(actual: StaticPoint2d, expected: StaticPoint2d) {
+        let StaticPoint2d { x, y } = actual.coordinates();
+        let StaticPoint2d { x: ex, y: ey } = expected.coordinates();
+        assert!(
+            (x.0 - ex.0).abs() < 1e-12 && (y.0 - ey.0).abs() < 1e-12,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_rotation_and_scaling() {
+        let rotation = Affine::rotation(&pt(1.0, 1.0), 90.0);
+        assert_near(rotation.apply(&pt(2.0, 1.0)), pt(1.0, 2.0));
+        assert_near(rotation.apply(&pt(1.0, 1.0)), pt(1.0, 1.0));
+
+        let scaling = Affine::scaling(&pt(1.0, 0.0), 3.0);
+        assert_near(scaling.apply(&pt(2.0, 1.0)), pt(4.0, 3.0));
+        assert_eq!(scaling.determinant(), 9.0);
+
+        assert_near(
+            Affine::mirror_x(&pt(1.0, 0.0)).apply(&pt(3.0, 5.0)),
+            pt(-1.0, 5.0),
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        let transform = Affine::translation(3.0, -2.0)
+            .then_after(&Affine::rotation(&pt(5.0, 7.0), 33.0))
+            .then_after(&Affine::scaling(&pt(-1.0, 2.0), 0.5));
+        let inverse = transform.inverse().unwrap();
+        let p = pt(12.5, -4.25);
+        assert_near(inverse.apply(&transform.apply(&p)), p);
+        assert_eq!(Affine::scaling(&p, 0.0).inverse(), None);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let triangle = StaticTriangle2d::new(pt(0.0, 0.0), pt(3.0, 0.0), pt(0.0, 3.0));
+        assert_eq!(centroid(&triangle), pt(1.0, 1.0));
+        let moved = Affine::translation(1.0, 2.0).apply_triangle(&triangle);
+        assert_eq!(*moved.p2(), pt(4.0, 2.0));
+    }
+}
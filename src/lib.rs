@@ -0,0 +1,4 @@
+pub mod display;
+pub mod geometry;
+pub mod render;
+pub mod scene;
@@ -11,5 +11,6 @@ const LOG_LEVEL: log::Level = log::Level::Trace;
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::new(LOG_LEVEL));
+    components::failure::install_panic_hook();
     yew::Renderer::<MainPage>::new().render();
 }
@@ -0,0 +1,247 @@
+use patternfly_yew::prelude::{Switch, Toolbar, ToolbarItem};
+use triangle_play::display::{CssColor, CssStyle, Figure};
+use triangle_play::geometry::containment::{classify, sample_grid, Containment};
+use triangle_play::geometry::snap::{SnapKind, SnapModes, Snapper};
+use triangle_play::scene::editor::{Editor, PointerInput};
+use triangle_play::scene::Scene;
+use triangles::prelude::{BoundingBox, Polygon2d, StaticPoint2d, Triangle2d};
+use web_sys::HtmlSelectElement;
+use yew::{function_component, html, use_memo, use_state, Callback, Event, Html, TargetCast};
+
+use crate::components::render2d::{CanvasMouseEvent, PolygonList, ProjectionConfig, Render2d};
+
+macro_rules! enclose {
+    ( ($( $x:ident ),*) $y:expr ) => {
+        {
+            $(let $x = $x.clone();)*
+            $y
+        }
+    };
+}
+
+/// Snap distance of the probe in pixels
+const PROBE_SNAP_RADIUS: f64 = 10.0;
+/// Size of the sample grid markers in pixels
+const SAMPLE_SIZE: f64 = 3.0;
+const GRID_STEPS: [usize; 4] = [20, 40, 80, 160];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Probe {
+    position: StaticPoint2d,
+    snapped_to: Option<SnapKind>,
+}
+
+#[function_component(Containment2d)]
+pub fn containment_2d() -> Html {
+    let scene = use_state(Scene::default);
+    // corners only snap to the grid, the probe snaps to the triangle itself
+    let editor = use_state(|| {
+        Editor::new(SnapModes {
+            grid: true,
+            integer: false,
+            vertices: false,
+            edges: false,
+            midpoints: false,
+            intersections: false,
+        })
+    });
+    let probe = use_state(|| None::<Probe>);
+    let show_grid = use_state(|| false);
+    let grid_steps = use_state(|| GRID_STEPS[1]);
+
+    let on_mouse_event = Callback::from(
+        enclose! {(scene, editor, probe) move |event: CanvasMouseEvent| {
+            let input = PointerInput {
+                position: (event.x(), event.y()).into(),
+                pressed: (event.buttons() & 1) != 0,
+                resolution: event.resolution().0,
+                grid_step: event.grid_step(),
+                snapping_disabled: event.shift_key(),
+            };
+            let mut next_editor = *editor;
+            let command = next_editor.pointer(&scene, &input);
+            if let Some(next_scene) = command.and_then(|command| scene.apply(&command)) {
+                scene.set(next_scene);
+            }
+            let next_probe = if next_editor.selected_corner().is_some() {
+                None
+            } else {
+                let snap = if input.snapping_disabled {
+                    None
+                } else {
+                    probe_snapper(&scene, &input).snap(&input.position)
+                };
+                Some(Probe {
+                    position: snap.map(|s| s.point).unwrap_or(input.position),
+                    snapped_to: snap.map(|s| s.kind),
+                })
+            };
+            if next_probe != *probe {
+                probe.set(next_probe);
+            }
+            if next_editor != *editor {
+                editor.set(next_editor);
+            }
+        }},
+    );
+    let on_grid_change = Callback::from(enclose! {(show_grid) move |checked: bool| {
+        show_grid.set(checked);
+    }});
+    let on_steps_change = Callback::from(enclose! {(grid_steps) move |event: Event| {
+        let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+        if let Ok(steps) = value.parse() {
+            grid_steps.set(steps);
+        }
+    }});
+
+    let triangle = scene.triangle;
+    let mut bbox = BoundingBox::default();
+    for pt in triangle.points() {
+        bbox += *pt;
+    }
+    // the probe follows the mouse anywhere on the canvas, so the view must not be
+    // fitted to the displayed figures
+    let bounds = match bbox {
+        BoundingBox::Box(bounds) => Some(bounds.expand(0.2.into())),
+        BoundingBox::Empty => None,
+    };
+    // the probe moves with every mouse event, the sample grid only with the triangle
+    let base = use_memo(
+        enclose! {(bounds) move |(scene, show_grid, grid_steps): &(Scene, bool, usize)| {
+            let triangle = scene.triangle;
+            let mut figure_list = Vec::new();
+            let mut grid_counts = String::new();
+            if let Some(bounds) = bounds.as_ref().filter(|_| *show_grid) {
+                let samples = sample_grid(&triangle, bounds, *grid_steps);
+                let mut groups: [(Containment, Vec<StaticPoint2d>); 4] = [
+                    (Containment::Outside, Vec::new()),
+                    (Containment::Inside, Vec::new()),
+                    (Containment::OnEdge(0), Vec::new()),
+                    (Containment::OnVertex(0), Vec::new()),
+                ];
+                for (pt, containment) in samples {
+                    let group = match containment {
+                        Containment::Outside => 0,
+                        Containment::Inside => 1,
+                        Containment::OnEdge(_) => 2,
+                        Containment::OnVertex(_) => 3,
+                    };
+                    groups[group].1.push(pt);
+                }
+                grid_counts = format!(
+                    "outside: {}, inside: {}, on edge: {}, on vertex: {}",
+                    groups[0].1.len(),
+                    groups[1].1.len(),
+                    groups[2].1.len(),
+                    groups[3].1.len()
+                );
+                for (containment, points) in groups {
+                    figure_list.push(Figure::points(
+                        containment_style(containment),
+                        points,
+                        SAMPLE_SIZE,
+                    ));
+                }
+            }
+            figure_list.push(Figure::polygon(
+                CssStyle::Color(CssColor::Blue),
+                triangle.to_any_polygon(),
+            ));
+            figure_list.extend(
+                triangle
+                    .points()
+                    .map(|pt| Figure::marker(CssStyle::Color(CssColor::Blue), *pt)),
+            );
+            let figures: PolygonList = figure_list.into();
+            (figures, grid_counts)
+        }},
+        (*scene, *show_grid, *grid_steps),
+    );
+    let (figures, grid_counts) = (*base).clone();
+    let mut overlay = Vec::new();
+    let probe_text = match *probe {
+        Some(probe) => {
+            let containment = classify(&triangle, &probe.position);
+            overlay.push(Figure::snap_marker(
+                containment_style(containment),
+                probe.position,
+            ));
+            let StaticPoint2d { x, y } = probe.position;
+            let mut text = format!("({:.3}, {:.3}): {containment:?}", x.0, y.0);
+            if let Some(kind) = probe.snapped_to {
+                text.push_str(&format!(", snapped to {kind:?}"));
+                if !consistent(kind, containment) {
+                    text.push_str(" (inconsistent)");
+                }
+            }
+            text
+        }
+        None => String::new(),
+    };
+    overlay.extend(editor.overlay(&scene));
+    let overlay: PolygonList = overlay.into();
+    let projection = ProjectionConfig {
+        world_bounds: bounds,
+        ..ProjectionConfig::default()
+    };
+
+    html! {
+        <>
+            <Toolbar>
+                <ToolbarItem>
+                    <Switch label="Sample grid" checked={*show_grid} onchange={on_grid_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <select onchange={on_steps_change}>
+                        { for GRID_STEPS.iter().map(|steps| html! {
+                            <option value={steps.to_string()} selected={*steps == *grid_steps}>
+                                {format!("{steps} x {steps}")}
+                            </option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>{grid_counts}</ToolbarItem>
+                <ToolbarItem>{probe_text}</ToolbarItem>
+            </Toolbar>
+            <Render2d polygons={figures} {overlay} {projection} {on_mouse_event}/>
+        </>
+    }
+}
+
+/// Lets the probe land exactly on the corners, edges and edge midpoints of the triangle
+fn probe_snapper(scene: &Scene, input: &PointerInput) -> Snapper {
+    let triangle = &scene.triangle;
+    Snapper::new(
+        SnapModes {
+            grid: false,
+            intersections: false,
+            ..SnapModes::default()
+        },
+        input.resolution * PROBE_SNAP_RADIUS,
+        input.grid_step,
+    )
+    .with_vertices(triangle.points())
+    .with_edges([
+        (triangle.p1(), triangle.p2()),
+        (triangle.p2(), triangle.p3()),
+        (triangle.p3(), triangle.p1()),
+    ])
+}
+
+/// Whether the classification matches the geometry the probe was snapped to
+fn consistent(kind: SnapKind, containment: Containment) -> bool {
+    match kind {
+        SnapKind::Vertex => matches!(containment, Containment::OnVertex(_)),
+        SnapKind::Midpoint | SnapKind::Edge => matches!(containment, Containment::OnEdge(_)),
+        _ => true,
+    }
+}
+
+fn containment_style(containment: Containment) -> CssStyle {
+    CssStyle::Color(match containment {
+        Containment::Inside => CssColor::Green,
+        Containment::Outside => CssColor::Black,
+        Containment::OnEdge(_) => CssColor::Blue,
+        Containment::OnVertex(_) => CssColor::Red,
+    })
+}
@@ -0,0 +1,134 @@
+use std::rc::Rc;
+
+use patternfly_yew::prelude::{Switch, Toolbar, ToolbarItem};
+use triangle_play::display::{Figure, Layer, Layers};
+use triangle_play::geometry::quality::MeshQuality;
+use triangle_play::scene::editor::PointerInput;
+use triangle_play::scene::points::{PointEditor, PointSet, PointTool, TriangulationLayers};
+use web_sys::HtmlSelectElement;
+use yew::{function_component, html, use_memo, use_state, Callback, Event, Html, TargetCast};
+
+use crate::components::render2d::{CanvasMouseEvent, PolygonList, Render2d};
+
+macro_rules! enclose {
+    ( ($( $x:ident ),*) $y:expr ) => {
+        {
+            $(let $x = $x.clone();)*
+            $y
+        }
+    };
+}
+
+const TOOLS: [(PointTool, &str); 3] = [
+    (PointTool::Place, "Place / drag points"),
+    (PointTool::Connect, "Constrain edges"),
+    (PointTool::Remove, "Remove points"),
+];
+
+#[function_component(Delaunay2d)]
+pub fn delaunay_2d() -> Html {
+    let points = use_state(PointSet::default);
+    let editor = use_state(PointEditor::default);
+    let layers = use_state(TriangulationLayers::default);
+
+    let on_mouse_event =
+        Callback::from(enclose! {(points, editor) move |event: CanvasMouseEvent| {
+            let mut next_editor = *editor;
+            let command = next_editor.pointer(&points, &PointerInput {
+                position: (event.x(), event.y()).into(),
+                pressed: (event.buttons() & 1) != 0,
+                resolution: event.resolution().0,
+                grid_step: event.grid_step(),
+                snapping_disabled: event.shift_key(),
+            });
+            if let Some(command) = command {
+                points.set(points.apply(&command));
+            }
+            if next_editor != *editor {
+                editor.set(next_editor);
+            }
+        }});
+    let on_tool_change = Callback::from(enclose! {(editor) move |event: Event| {
+        let selected = event.target_unchecked_into::<HtmlSelectElement>().selected_index();
+        if let Some((tool, _)) = usize::try_from(selected).ok().and_then(|idx| TOOLS.get(idx)) {
+            let mut next_editor = *editor;
+            next_editor.select_tool(*tool);
+            editor.set(next_editor);
+        }
+    }});
+    let layer_toggle = |update: fn(&mut TriangulationLayers, bool)| {
+        Callback::from(enclose! {(layers) move |checked: bool| {
+            let mut next_layers = *layers;
+            update(&mut next_layers, checked);
+            layers.set(next_layers);
+        }})
+    };
+    let on_clear_constraints = Callback::from(enclose! {(points) move |_| {
+        points.set(PointSet {
+            constraints: Vec::new(),
+            ..(*points).clone()
+        });
+    }});
+
+    // hovering only changes the editor, the triangulation follows the points
+    let derived = use_memo(
+        |(points, layers): &(PointSet, TriangulationLayers)| {
+            let triangulation = points.triangulate();
+            let triangles = triangulation.to_triangles(&points.points);
+            let metrics = match MeshQuality::of(&triangles) {
+                Some(quality) => format!(
+                    "triangles: {}, min angle: {:.1}°, max angle: {:.1}°, mean min angle: {:.1}°, worst radius ratio: {:.2}, poor: {}",
+                    quality.triangles,
+                    quality.min_angle,
+                    quality.max_angle,
+                    quality.mean_min_angle,
+                    quality.max_radius_ratio,
+                    quality.poor_triangles
+                ),
+                None => "triangles: 0".to_string(),
+            };
+            let constraints = format!(
+                "constraints: {} kept, {} rejected",
+                triangulation.constrained.len(),
+                triangulation.rejected.len()
+            );
+            let figures: PolygonList = points.figures(&triangulation, *layers).into();
+            let circles: Rc<[Figure]> = points.circumcircle_figures(&triangulation).into();
+            (figures, circles, metrics, constraints)
+        },
+        ((*points).clone(), *layers),
+    );
+    let (figures, circles, metrics, constraints) = (*derived).clone();
+    // fitting the view to the circles would zoom out whenever a triangle gets flat
+    let circle_layers = Layers::default()
+        .with(Layer::overlay("circumcircles", 1, circles).with_visible(layers.circumcircles));
+    let overlay: PolygonList = editor.overlay(&points).into();
+
+    html! {
+        <>
+            <Toolbar>
+                <ToolbarItem>
+                    <select onchange={on_tool_change}>
+                        { for TOOLS.iter().map(|(tool, name)| html! {
+                            <option selected={*tool == editor.tool}>{name}</option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Circumcircles" checked={layers.circumcircles} onchange={layer_toggle(|l, v| l.circumcircles = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Quality colors" checked={layers.quality} onchange={layer_toggle(|l, v| l.quality = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button onclick={on_clear_constraints}>{"Clear constraints"}</button>
+                </ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>{metrics}</ToolbarItem>
+                <ToolbarItem>{constraints}</ToolbarItem>
+            </Toolbar>
+            <Render2d polygons={figures} layers={circle_layers} {overlay} {on_mouse_event}/>
+        </>
+    }
+}
@@ -0,0 +1,140 @@
+use patternfly_yew::prelude::{Switch, Toolbar, ToolbarItem};
+use triangle_play::geometry::quality::MeshQuality;
+use triangle_play::scene::editor::PointerInput;
+use triangle_play::scene::polygon::{PolygonCommand, PolygonEditor, PolygonScene};
+use yew::{function_component, html, use_memo, use_state, Callback, Html};
+
+use crate::components::render2d::{CanvasMouseEvent, PolygonList, Render2d};
+
+macro_rules! enclose {
+    ( ($( $x:ident ),*) $y:expr ) => {
+        {
+            $(let $x = $x.clone();)*
+            $y
+        }
+    };
+}
+
+#[function_component(EarClipping2d)]
+pub fn ear_clipping_2d() -> Html {
+    let polygon = use_state(PolygonScene::default);
+    let editor = use_state(PolygonEditor::default);
+    // `None` shows the finished triangulation
+    let step = use_state(|| None::<usize>);
+    let show_quality = use_state(|| true);
+
+    let on_mouse_event =
+        Callback::from(enclose! {(polygon, editor) move |event: CanvasMouseEvent| {
+            let mut next_editor = *editor;
+            let command = next_editor.pointer(&polygon, &PointerInput {
+                position: (event.x(), event.y()).into(),
+                pressed: (event.buttons() & 1) != 0,
+                resolution: event.resolution().0,
+                grid_step: event.grid_step(),
+                snapping_disabled: event.shift_key(),
+            });
+            if let Some(command) = command {
+                polygon.set(polygon.apply(&command));
+            }
+            if next_editor != *editor {
+                editor.set(next_editor);
+            }
+        }});
+    let command_button = |command: PolygonCommand| {
+        Callback::from(enclose! {(polygon, step) move |_| {
+            polygon.set(polygon.apply(&command));
+            step.set(None);
+        }})
+    };
+    let on_step_mode_change = Callback::from(enclose! {(step) move |checked: bool| {
+        step.set(if checked { Some(0) } else { None });
+    }});
+    let on_quality_change = Callback::from(enclose! {(show_quality) move |checked: bool| {
+        show_quality.set(checked);
+    }});
+
+    // hovering only changes the editor, the triangulation follows the polygon
+    let clipping = use_memo(
+        |polygon: &PolygonScene| polygon.triangulate(),
+        (*polygon).clone(),
+    );
+    let last_step = clipping.ears.len().saturating_sub(1);
+    let step_button = |next: fn(usize) -> usize| {
+        Callback::from(enclose! {(step) move |_| {
+            if let Some(current) = *step {
+                step.set(Some(next(current).min(last_step)));
+            }
+        }})
+    };
+    let texts = use_memo(
+        enclose! {(clipping) move |polygon: &PolygonScene| {
+            let polygon_area = polygon.area();
+            let triangle_area = clipping.triangle_area();
+            // relative tolerance, the areas are sums of differently rounded terms
+            let area_valid = (polygon_area - triangle_area).abs() <= 1e-9 * polygon_area.abs().max(1.0);
+            let validation = format!(
+                "polygon area: {:.3}, triangle area: {:.3}{}{}",
+                polygon_area,
+                triangle_area,
+                if area_valid { "" } else { " (mismatch)" },
+                if clipping.complete {
+                    ""
+                } else {
+                    ", no ear found"
+                }
+            );
+            let metrics = match MeshQuality::of(&clipping.triangles()) {
+                Some(quality) => format!(
+                    "triangles: {}, min angle: {:.1}°, poor: {}",
+                    quality.triangles, quality.min_angle, quality.poor_triangles
+                ),
+                None => "triangles: 0".to_string(),
+            };
+            (validation, metrics)
+        }},
+        (*polygon).clone(),
+    );
+    let (validation, metrics) = (*texts).clone();
+    let step_text = match *step {
+        Some(current) => format!("ear {} of {}", current + 1, clipping.ears.len()),
+        None => String::new(),
+    };
+    let figures = use_memo(
+        enclose! {(clipping) move |(polygon, step, show_quality): &(PolygonScene, Option<usize>, bool)| {
+            PolygonList::from(polygon.figures(&clipping, *step, *show_quality))
+        }},
+        ((*polygon).clone(), *step, *show_quality),
+    );
+    let overlay: PolygonList = editor.overlay(&polygon).into();
+
+    html! {
+        <>
+            <Toolbar>
+                <ToolbarItem>
+                    <button onclick={command_button(PolygonCommand::StartHole)}>{"New hole"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button onclick={command_button(PolygonCommand::Clear)}>{"Clear"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Step mode" checked={step.is_some()} onchange={on_step_mode_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button disabled={step.is_none()} onclick={step_button(|s| s.saturating_sub(1))}>{"Previous"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button disabled={step.is_none()} onclick={step_button(|s| s + 1)}>{"Next"}</button>
+                </ToolbarItem>
+                <ToolbarItem>{step_text}</ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Quality colors" checked={*show_quality} onchange={on_quality_change}/>
+                </ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>{metrics}</ToolbarItem>
+                <ToolbarItem>{validation}</ToolbarItem>
+            </Toolbar>
+            <Render2d polygons={(*figures).clone()} {overlay} {on_mouse_event}/>
+        </>
+    }
+}
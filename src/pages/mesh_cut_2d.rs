@@ -0,0 +1,260 @@
+use std::rc::Rc;
+
+use gloo::utils::window;
+use patternfly_yew::prelude::{Switch, Toolbar, ToolbarItem};
+use triangle_play::display::{Figure, Layer, Layers};
+use triangle_play::scene::compute::{Answer, Job};
+use triangle_play::scene::editor::PointerInput;
+use triangle_play::scene::file::MeshFile;
+use triangle_play::scene::mesh::{
+    cutter_figures, default_cutter, grid_mesh, input_figures, mesh_area, mesh_figures, MeshCut,
+};
+use triangle_play::scene::polygon::{PolygonCommand, PolygonEditor, PolygonScene};
+use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::{
+    function_component, html, use_effect_with_deps, use_memo, use_mut_ref, use_state, Callback,
+    Event, Html, TargetCast, UseStateHandle,
+};
+
+use crate::components::compute::use_worker;
+use crate::components::render2d::{CanvasMouseEvent, PolygonList, Render2d};
+
+macro_rules! enclose {
+    ( ($( $x:ident ),*) $y:expr ) => {
+        {
+            $(let $x = $x.clone();)*
+            $y
+        }
+    };
+}
+
+type Mesh = Rc<[StaticTriangle2d<StaticPoint2d>]>;
+
+/// Width of the grid cells
+const CELL_SIZE: f64 = 10.0;
+
+/// Cells per row and column of the largest grid, which makes 80000 triangles
+const MAX_GRID: usize = 200;
+
+/// Cutting the largest grids takes several seconds, a panic is noticed only after this
+const MESH_TIMEOUT_MS: u32 = 60_000;
+
+/// Milliseconds since the page was loaded
+fn now() -> f64 {
+    window()
+        .performance()
+        .map_or(0.0, |performance| performance.now())
+}
+
+/// The cut and the figures of its result
+#[derive(Clone, PartialEq)]
+struct ComputedCut {
+    mesh: Mesh,
+    cut: MeshCut,
+    /// Milliseconds from the last change of the mesh or the cutter until the worker
+    /// answered, which includes waiting for older cuts and sending the triangles
+    round_trip_millis: f64,
+    inside: Rc<[Figure]>,
+    outside: Rc<[Figure]>,
+    failures: Rc<[Figure]>,
+    /// Why the worker gave no cut
+    error: Option<String>,
+}
+
+#[function_component(MeshCut2d)]
+pub fn mesh_cut_2d() -> Html {
+    let grid = use_state(|| (10_usize, 10_usize));
+    let mesh = use_state(|| Mesh::from(grid_mesh(10, 10, CELL_SIZE)));
+    let cutter = use_state(default_cutter);
+    let editor = use_state(PolygonEditor::default);
+    let mesh_json = use_state(String::new);
+    let load_error = use_state(|| None::<String>);
+    let show_mesh = use_state(|| false);
+    let show_inside = use_state(|| true);
+    let show_outside = use_state(|| true);
+
+    let on_mouse_event =
+        Callback::from(enclose! {(cutter, editor) move |event: CanvasMouseEvent| {
+            let mut next_editor = *editor;
+            let command = next_editor.pointer(&cutter, &PointerInput {
+                position: (event.x(), event.y()).into(),
+                pressed: (event.buttons() & 1) != 0,
+                resolution: event.resolution().0,
+                grid_step: event.grid_step(),
+                snapping_disabled: event.shift_key(),
+            });
+            if let Some(command) = command {
+                cutter.set(cutter.apply(&command));
+            }
+            if next_editor != *editor {
+                editor.set(next_editor);
+            }
+        }});
+    let command_button = |command: PolygonCommand| {
+        Callback::from(enclose! {(cutter) move |_| {
+            cutter.set(cutter.apply(&command));
+        }})
+    };
+    let grid_input = |update: fn(&mut (usize, usize), usize)| {
+        Callback::from(enclose! {(grid) move |event: Event| {
+            let value = event.target_unchecked_into::<HtmlInputElement>().value_as_number();
+            if value.is_finite() {
+                let mut next_grid = *grid;
+                update(&mut next_grid, (value as usize).clamp(1, MAX_GRID));
+                grid.set(next_grid);
+            }
+        }})
+    };
+    let on_generate_grid = Callback::from(enclose! {(grid, mesh, load_error) move |_| {
+        let (columns, rows) = *grid;
+        mesh.set(grid_mesh(columns, rows, CELL_SIZE).into());
+        load_error.set(None);
+    }});
+    let on_mesh_json_change = Callback::from(enclose! {(mesh_json) move |event: Event| {
+        mesh_json.set(event.target_unchecked_into::<HtmlTextAreaElement>().value());
+    }});
+    let on_load_mesh = Callback::from(enclose! {(mesh_json, mesh, load_error) move |_| {
+        match MeshFile::from_json(&mesh_json) {
+            Ok(file) => {
+                mesh.set(file.to_triangles().into());
+                load_error.set(None);
+            }
+            Err(error) => load_error.set(Some(error.to_string())),
+        }
+    }});
+    let switch = |state: &UseStateHandle<bool>| {
+        Callback::from(enclose! {(state) move |checked: bool| state.set(checked)})
+    };
+
+    // hovering only changes the editor, the cut follows the mesh and the cutter. It runs
+    // in the worker, while dragging only the latest cutter is cut next.
+    let input = ((*mesh).clone(), (*cutter).clone());
+    let submitted_at = use_mut_ref(now);
+    use_effect_with_deps(
+        enclose! {(submitted_at) move |_: &(Mesh, PolygonScene)| {
+            *submitted_at.borrow_mut() = now();
+        }},
+        input.clone(),
+    );
+    let answer = use_worker(
+        Some(input),
+        |(mesh, cutter)| Job::Mesh {
+            mesh: MeshFile::from(mesh.as_ref()),
+            cutter: cutter.clone(),
+        },
+        |answer| match answer {
+            Answer::Mesh(cut) => Some(cut),
+            _ => None,
+        },
+        MESH_TIMEOUT_MS,
+    );
+    let (busy, generation) = (answer.busy, answer.generation);
+    let computed = use_memo(
+        enclose! {(submitted_at) move |_: &u64| {
+            let round_trip_millis = now() - *submitted_at.borrow();
+            let mesh = answer.input.map_or_else(|| Mesh::from(Vec::new()), |(mesh, _)| mesh);
+            let (cut, error) = match answer.output {
+                Some(Ok(cut)) => (cut, None),
+                Some(Err(message)) => (MeshCut::default(), Some(message)),
+                None => (MeshCut::default(), None),
+            };
+            let (inside, outside) = mesh_figures(&cut.result);
+            ComputedCut {
+                mesh,
+                round_trip_millis,
+                inside: inside.into(),
+                outside: outside.into(),
+                failures: cut.failure_figures().into(),
+                cut,
+                error,
+            }
+        }},
+        generation,
+    );
+    let mesh_outlines = use_memo(
+        |mesh: &Mesh| -> Rc<[Figure]> { input_figures(mesh).into() },
+        (*mesh).clone(),
+    );
+    let cut = &computed.cut;
+    let stats = format!(
+        "mesh: {} triangles, cutter: {} triangles{}, cuts: {}, cut time: {:.1} ms, round trip: {:.1} ms{}",
+        computed.mesh.len(),
+        cut.cutter.ears.len(),
+        if cut.cutter.complete {
+            ""
+        } else {
+            " (no ear found)"
+        },
+        cut.cuts,
+        cut.millis,
+        computed.round_trip_millis,
+        if busy { ", cutting" } else { "" }
+    );
+    let area = mesh_area(&computed.mesh);
+    let (inside_area, outside_area) = (cut.result.inside_area(), cut.result.outside_area());
+    // relative tolerance, the areas are sums of many differently rounded terms
+    let area_valid = (inside_area + outside_area - area).abs() <= 1e-9 * area.max(1.0);
+    let result_text = match &computed.error {
+        Some(message) => format!("mesh cut failed: {message}"),
+        _ => format!(
+            "inside: {} ({:.2}), outside: {} ({:.2}), mesh area: {:.2}{}, failed cuts: {}",
+            cut.result.inside.len(),
+            inside_area,
+            cut.result.outside.len(),
+            outside_area,
+            area,
+            if area_valid { "" } else { " (mismatch)" },
+            cut.failures.len()
+        ),
+    };
+    let load_text = (*load_error).clone().unwrap_or_default();
+
+    let layers = Layers::default()
+        .with(Layer::new("mesh", 0, (*mesh_outlines).clone()).with_visible(*show_mesh))
+        .with(Layer::new("outside", 1, computed.outside.clone()).with_visible(*show_outside))
+        .with(Layer::new("inside", 2, computed.inside.clone()).with_visible(*show_inside))
+        .with(Layer::new("failures", 3, computed.failures.clone()))
+        .with(Layer::new("cutter", 4, cutter_figures(&cutter)));
+    let overlay: PolygonList = editor.overlay(&cutter).into();
+    let (columns, rows) = *grid;
+
+    html! {
+        <>
+            <Toolbar>
+                <ToolbarItem>
+                    <input type="number" min="1" max={MAX_GRID.to_string()} value={columns.to_string()} onchange={grid_input(|g, v| g.0 = v)}/>
+                    <input type="number" min="1" max={MAX_GRID.to_string()} value={rows.to_string()} onchange={grid_input(|g, v| g.1 = v)}/>
+                    <button onclick={on_generate_grid}>{"Generate grid"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <textarea placeholder={r#"{"triangles": [[[0, 0], [10, 0], [0, 10]]]}"#} value={(*mesh_json).clone()} onchange={on_mesh_json_change}/>
+                    <button onclick={on_load_mesh}>{"Load mesh"}</button>
+                </ToolbarItem>
+                <ToolbarItem>{load_text}</ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <button onclick={command_button(PolygonCommand::StartHole)}>{"New hole"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button onclick={command_button(PolygonCommand::Clear)}>{"Clear cutter"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Mesh" checked={*show_mesh} onchange={switch(&show_mesh)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Inside" checked={*show_inside} onchange={switch(&show_inside)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Outside" checked={*show_outside} onchange={switch(&show_outside)}/>
+                </ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>{stats}</ToolbarItem>
+                <ToolbarItem>{result_text}</ToolbarItem>
+            </Toolbar>
+            <Render2d {layers} {overlay} {on_mouse_event}/>
+        </>
+    }
+}
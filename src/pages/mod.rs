@@ -7,9 +7,15 @@ use yew::{html, Callback, Html, MouseEvent};
 use yew_nested_router::Router;
 use yew_nested_router::Switch as RouterSwitch;
 
+use crate::components::failure::CrashReportToast;
+use crate::components::theme::{ThemeProvider, ThemeSwitches};
 use crate::route::switch_main;
 use crate::route::AppRoute;
 
+pub mod containment_2d;
+pub mod delaunay_2d;
+pub mod ear_clipping_2d;
+pub mod mesh_cut_2d;
 pub mod test3d;
 pub mod triangle_cut_2d;
 
@@ -18,15 +24,18 @@ pub fn main_page() -> Html {
     html! {
         <BackdropViewer>
             <ToastViewer>
-                <Router<AppRoute>>
-                    <Page sidebar={html_nested! {<PageSidebar><Sidebar/></PageSidebar>}}>
-                      //<ToastViewer/>
-                      //logo={logo}
-                        <RouterSwitch<AppRoute>
-                            render = { switch_main}
-                        />
-                    </Page>
-                </Router<AppRoute>>
+                <CrashReportToast/>
+                <ThemeProvider>
+                    <Router<AppRoute>>
+                        <Page sidebar={html_nested! {<PageSidebar><Sidebar/></PageSidebar>}}>
+                          //<ToastViewer/>
+                          //logo={logo}
+                            <RouterSwitch<AppRoute>
+                                render = { switch_main}
+                            />
+                        </Page>
+                    </Router<AppRoute>>
+                </ThemeProvider>
             </ToastViewer>
         </BackdropViewer>
     }
@@ -36,10 +45,17 @@ pub fn main_page() -> Html {
 fn authenticated_sidebar() -> Html {
     let logout = Callback::from(move |_: MouseEvent| {});
     html! {
-        <Nav>
-            <NavRouterItem<AppRoute> to={AppRoute::Basic2d}>{"2D Rendering"}</NavRouterItem<AppRoute>>
-            <NavRouterItem<AppRoute> to={AppRoute::Test3d}>{"3D Test"}</NavRouterItem<AppRoute>>
-            <span onclick={logout}><NavItem>{"Logout"}</NavItem></span>
-        </Nav>
+        <>
+            <Nav>
+                <NavRouterItem<AppRoute> to={AppRoute::Basic2d}>{"2D Rendering"}</NavRouterItem<AppRoute>>
+                <NavRouterItem<AppRoute> to={AppRoute::Containment2d}>{"Containment"}</NavRouterItem<AppRoute>>
+                <NavRouterItem<AppRoute> to={AppRoute::Delaunay2d}>{"Delaunay"}</NavRouterItem<AppRoute>>
+                <NavRouterItem<AppRoute> to={AppRoute::EarClipping2d}>{"Ear clipping"}</NavRouterItem<AppRoute>>
+                <NavRouterItem<AppRoute> to={AppRoute::MeshCut2d}>{"Mesh cut"}</NavRouterItem<AppRoute>>
+                <NavRouterItem<AppRoute> to={AppRoute::Test3d}>{"3D Test"}</NavRouterItem<AppRoute>>
+                <span onclick={logout}><NavItem>{"Logout"}</NavItem></span>
+            </Nav>
+            <ThemeSwitches/>
+        </>
     }
 }
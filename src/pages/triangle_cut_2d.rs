@@ -1,13 +1,34 @@
 use std::ops::Deref;
 use std::rc::Rc;
 
-use num_traits::Zero;
-use triangles::prelude::{Number, Point2d, Polygon2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
-use yew::{function_component, html, use_state, Callback, Html, UseStateHandle};
+use patternfly_yew::prelude::{use_toaster, Spinner, SpinnerSize, Switch, Toolbar, ToolbarItem};
+use triangle_play::display::{Figure, Layer, Layers};
+use triangle_play::geometry::cut::CutResult;
+use triangle_play::geometry::exact::PrecisionReport;
+use triangle_play::geometry::measure::{TriangleMetrics, WindingCounts};
+use triangle_play::geometry::presets::{Preset, PRESETS};
+use triangle_play::geometry::snap::SnapModes;
+use triangle_play::geometry::transform::Affine;
+use triangle_play::scene::compute::{Answer, Job};
+use triangle_play::scene::editor::{EditTool, Editor, PointerInput};
+use triangle_play::scene::file::SceneFile;
+use triangle_play::scene::measure::{MeasureTool, Measurer};
+use triangle_play::scene::reproduction::cut_test;
+use triangle_play::scene::sweep::Sweep;
+use triangle_play::scene::{precision_figures, Command, Scene, SceneLayer, Shape};
+use triangles::prelude::StaticPoint2d;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{
+    function_component, html, use_effect_with_deps, use_memo, use_state, Callback, Event, Html,
+    TargetCast,
+};
 
-use crate::components::render2d::{
-    CanvasMouseEvent, CssColor, CssStyle, Figure, PolygonList, Render2d,
+use crate::components::compute::{use_worker, use_worker_cut, use_worker_sweep, WORKER_TIMEOUT_MS};
+use crate::components::failure::{
+    copy_to_clipboard, failure_toast, remember_edited_scene, take_restored_scene,
 };
+use crate::components::render2d::{CanvasMouseEvent, PolygonList, ProjectionConfig, Render2d};
+use crate::components::split_view::{Panel, SplitView};
 
 macro_rules! enclose {
     ( ($( $x:ident ),*) $y:expr ) => {
@@ -18,120 +39,608 @@ macro_rules! enclose {
     };
 }
 
-#[function_component(TriangleCut2d)]
-pub fn triangle_cut_2d() -> Html {
-    let big_triangle_state = use_state(|| {
-        StaticTriangle2d::new(
-            (-100.0, 0.0).into(),
-            (100.0, 0.0).into(),
-            (0.0, 100.0).into(),
-        )
-    });
-    let big_triangle = *big_triangle_state;
-    let small_triangle = Rc::new(StaticTriangle2d::<StaticPoint2d>::new(
-        (-50.0, 25.0).into(),
-        (00.0, -25.0).into(),
-        (50.0, 25.0).into(),
-    ));
-    let generate_cutting_triangles = enclose! {(big_triangle_state) move || {
-            let cut_polygon = small_triangle.clone();
-            let path = big_triangle.cut(cut_polygon.deref());
+/// Cells per row and column of the robustness heat map
+const SWEEP_STEPS: usize = 40;
 
-            let mut figure_list = vec![
-                /*
-                Figure::polygon(
-                    CssStyle::Color(CssColor::Blue),
-                    big_triangle_state.clone().to_any_polygon(),
-                ),
-                Figure::polygon(
-                    CssStyle::Color(CssColor::Green),
-                    small_triangle.to_any_polygon(),
-                ),*/
-            ];
-            for pt in big_triangle_state.points(){
-                figure_list.push(Figure::marker(CssStyle::Color(CssColor::Blue),*pt));
-            }
-            //figure_list.clear();
+const EDIT_TOOLS: [(EditTool, &str); 4] = [
+    (EditTool::Corners, "Move corners"),
+    (EditTool::Translate, "Translate"),
+    (EditTool::Rotate, "Rotate"),
+    (EditTool::Scale, "Scale"),
+];
 
-            let triangles = big_triangle.cut_to_triangles(cut_polygon.as_ref());;
-            for (triangles,style) in triangles.iter().zip([CssStyle::Color(CssColor::Green),CssStyle::Color(CssColor::Red)].iter()) {
-                for triangle in triangles{
-                    let triangle=triangle.coordinates_triangle();
-                    figure_list.push(Figure::polygon(style.clone(), triangle.to_any_polygon()));
-                }
-            }
-    /*
-        let polygons =  big_triangle.compose_cut_polygons(cut_polygon.as_ref(), &path);
-        for (polygons,style) in polygons.iter().zip([CssStyle::Color(CssColor::Green),CssStyle::Color(CssColor::Red)].iter()) {
-                for polygon in polygons{
-                figure_list.push(Figure::polygon(style.clone(),polygon.clone().to_any_polygon()));
-                    }
-        }*/
+const SHAPES: [(Shape, &str); 2] = [(Shape::Triangle, "Triangle"), (Shape::Cutter, "Cutter")];
 
-            figure_list
-        }};
+/// Values of the numeric transform entry
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct TransformEntry {
+    shape: Shape,
+    degrees: f64,
+    factor: f64,
+    dx: f64,
+    dy: f64,
+}
 
-    let current_selection = use_state(|| None);
+impl Default for TransformEntry {
+    fn default() -> Self {
+        Self {
+            shape: Shape::Triangle,
+            degrees: 90.0,
+            factor: 2.0,
+            dx: 10.0,
+            dy: 0.0,
+        }
+    }
+}
 
-    let polygons: UseStateHandle<PolygonList> = use_state(|| generate_cutting_triangles().into());
-    let update_polygons = enclose! {(polygons)
-    move | found_idx: Option<usize>, big_triangle: &StaticTriangle2d<StaticPoint2d>| {
-        let figure_list = generate_cutting_triangles();
-        let found = found_idx.and_then(|idx| big_triangle.get_point(idx));
+const MEASURE_TOOLS: [(MeasureTool, &str); 3] = [
+    (MeasureTool::Distance, "Distance"),
+    (MeasureTool::Angle, "Angle"),
+    (MeasureTool::Figure, "Figure"),
+];
 
-        if let Some(marker_pos) = found {
-            let marker = Figure::marker(CssStyle::Color(CssColor::Green), *marker_pos);
-            let mut new_list = figure_list.clone();
-            new_list.push(marker);
-            polygons.set(Into::<PolygonList>::into(new_list))
-        } else {
-            polygons.set(Into::<PolygonList>::into(figure_list.clone()));
-        }
-    }};
-    let on_mouse_event = Callback::from(
-        enclose! {(big_triangle_state) move |event: CanvasMouseEvent| {
-            let last_selection = *current_selection.deref();
-                    let mouse_pt = (event.x(), event.y()).into();
-            if (event.buttons() & 1) != 0 {
-                if let Some(selected_corner) = last_selection {
-                    let original_triangle = *big_triangle_state;
-                    let p1 = if selected_corner != 0 {
-                        original_triangle.p1()
-                    } else {
-                        &mouse_pt
-                    };
-                    let p2 = if selected_corner != 1 {
-                        original_triangle.p2()
-                    } else {
-                        &mouse_pt
-                    };
-                    let p3 = if selected_corner != 2 {
-                        original_triangle.p3()
-                    } else {
-                        &mouse_pt
-                    };
-                    let moved_triangle = StaticTriangle2d::new(*p1,*p2,*p3);
-                    if moved_triangle.area()>Number::zero(){
+#[function_component(TriangleCut2d)]
+pub fn triangle_cut_2d() -> Html {
+    let scene = use_state(|| take_restored_scene().unwrap_or_default());
+    let editor = use_state(Editor::default);
+    let show_annotations = use_state(|| true);
+    let show_winding = use_state(|| false);
+    let split_view = use_state(|| false);
+    let show_precision = use_state(|| false);
+    let sweep_corner = use_state(|| 0_usize);
+    let sweep_request = use_state(|| None::<Sweep>);
+    let show_heat_map = use_state(|| true);
+    let invariance_scene = use_state(|| None::<Scene>);
+    let selected_deviation = use_state(|| 0_usize);
+    let toaster = use_toaster();
+    let measuring = use_state(|| false);
+    let measurer = use_state(Measurer::default);
+    let entry = use_state(TransformEntry::default);
+    let computed = use_worker_cut(*scene);
+    use_effect_with_deps(remember_edited_scene, *scene);
+    let swept = use_worker_sweep((*sweep_request).clone());
 
-                        update_polygons(last_selection, big_triangle_state.deref());
-                        big_triangle_state.set( moved_triangle);
-                    };
-                    return ;
+    let on_mouse_event = Callback::from(
+        enclose! {(scene, editor, measuring, measurer, computed) move |event: CanvasMouseEvent| {
+            let input = PointerInput {
+                position: (event.x(), event.y()).into(),
+                pressed: (event.buttons() & 1) != 0,
+                resolution: event.resolution().0,
+                grid_step: event.grid_step(),
+                // holding shift places the corner exactly at the mouse position
+                snapping_disabled: event.shift_key(),
+            };
+            if *measuring {
+                let mut targets = vec![scene.triangle, scene.cutter];
+                if let Some(Ok(result)) = &computed.result {
+                    targets.extend(result.inside.iter().chain(result.outside.iter()));
                 }
-            }
-            let mut found_idx = None;
-            let r = event.resolution() * event.resolution() * 100.0;
-            for (idx, pt) in big_triangle.points().enumerate() {
-                if r >= pt.dist_square(&mouse_pt) {
-                    found_idx = Some(idx);
+                let mut next_measurer = (*measurer).clone();
+                next_measurer.pointer(&targets, &input);
+                if next_measurer != *measurer {
+                    measurer.set(next_measurer);
                 }
-              }
-            if last_selection != found_idx {
-                current_selection.set(found_idx);
-                update_polygons(found_idx, big_triangle_state.deref());
+                return;
+            }
+            let mut next_editor = *editor;
+            let command = next_editor.pointer(&scene, &input);
+            if let Some(next_scene) = command.and_then(|command| scene.apply(&command)) {
+                scene.set(next_scene);
+            }
+            if next_editor != *editor {
+                editor.set(next_editor);
+            }
+        }},
+    );
+    let on_measuring_change =
+        Callback::from(enclose! {(measuring, measurer) move |checked: bool| {
+            measuring.set(checked);
+            // starts over with the same tool
+            let mut next_measurer = (*measurer).clone();
+            next_measurer.select_tool(measurer.tool);
+            measurer.set(next_measurer);
+        }});
+    let on_measure_tool_change = Callback::from(enclose! {(measurer) move |event: Event| {
+        let selected = event.target_unchecked_into::<HtmlSelectElement>().selected_index();
+        if let Some((tool, _)) = usize::try_from(selected).ok().and_then(|idx| MEASURE_TOOLS.get(idx)) {
+            let mut next_measurer = (*measurer).clone();
+            next_measurer.select_tool(*tool);
+            measurer.set(next_measurer);
+        }
+    }});
+    let snap_toggle = |update: fn(&mut SnapModes, bool)| {
+        Callback::from(enclose! {(editor) move |checked: bool| {
+            let mut next_editor = *editor;
+            update(&mut next_editor.snap_modes, checked);
+            editor.set(next_editor);
+        }})
+    };
+    let on_annotations_change = Callback::from(enclose! {(show_annotations) move |checked: bool| {
+        show_annotations.set(checked);
+    }});
+    let on_winding_change = Callback::from(enclose! {(show_winding) move |checked: bool| {
+        show_winding.set(checked);
+    }});
+    let flip = |shape: Shape| {
+        Callback::from(enclose! {(scene) move |_| {
+            if let Some(next_scene) = scene.apply(&Command::FlipOrientation(shape)) {
+                scene.set(next_scene);
             }
+        }})
+    };
+    let on_split_view_change = Callback::from(enclose! {(split_view) move |checked: bool| {
+        split_view.set(checked);
+    }});
+    let on_precision_change = Callback::from(enclose! {(show_precision) move |checked: bool| {
+        show_precision.set(checked);
+    }});
+    let on_sweep_corner_change = Callback::from(enclose! {(sweep_corner) move |event: Event| {
+        let selected = event.target_unchecked_into::<HtmlSelectElement>().selected_index();
+        if let Ok(corner) = usize::try_from(selected) {
+            sweep_corner.set(corner);
+        }
+    }});
+    // one cut per cell, so the sweep only runs on request
+    let on_run_sweep = Callback::from(enclose! {(scene, sweep_corner, sweep_request) move |_| {
+        if let Some(bounds) = Sweep::region(&scene) {
+            sweep_request.set(Some(Sweep::new(&scene, *sweep_corner, bounds, SWEEP_STEPS)));
+        }
+    }});
+    let on_heat_map_change = Callback::from(enclose! {(show_heat_map) move |checked: bool| {
+        show_heat_map.set(checked);
+    }});
+    // about a hundred cuts, so the check only runs on request
+    let on_check_invariance = Callback::from(
+        enclose! {(scene, invariance_scene, selected_deviation) move |_| {
+            invariance_scene.set(Some(*scene));
+            selected_deviation.set(0);
         }},
     );
-    let p = polygons.deref().clone();
-    html! {<Render2d  polygons={p} {on_mouse_event}/>}
+    let on_deviation_change = Callback::from(enclose! {(selected_deviation) move |event: Event| {
+        let selected = event.target_unchecked_into::<HtmlSelectElement>().selected_index();
+        if let Ok(selected) = usize::try_from(selected) {
+            selected_deviation.set(selected);
+        }
+    }});
+    let on_edit_tool_change = Callback::from(enclose! {(editor) move |event: Event| {
+        let selected = event.target_unchecked_into::<HtmlSelectElement>().selected_index();
+        if let Some((tool, _)) = usize::try_from(selected).ok().and_then(|idx| EDIT_TOOLS.get(idx)) {
+            let mut next_editor = *editor;
+            next_editor.select_tool(*tool);
+            editor.set(next_editor);
+        }
+    }});
+    let on_reset_pivot = Callback::from(enclose! {(editor) move |_| {
+        let mut next_editor = *editor;
+        next_editor.reset_pivot();
+        editor.set(next_editor);
+    }});
+    let on_entry_shape_change = Callback::from(enclose! {(entry) move |event: Event| {
+        let selected = event.target_unchecked_into::<HtmlSelectElement>().selected_index();
+        if let Some((shape, _)) = usize::try_from(selected).ok().and_then(|idx| SHAPES.get(idx)) {
+            entry.set(TransformEntry { shape: *shape, ..*entry });
+        }
+    }});
+    let entry_input = |update: fn(&mut TransformEntry, f64)| {
+        Callback::from(enclose! {(entry) move |event: Event| {
+            let value = event.target_unchecked_into::<HtmlInputElement>().value_as_number();
+            if value.is_finite() {
+                let mut next_entry = *entry;
+                update(&mut next_entry, value);
+                entry.set(next_entry);
+            }
+        }})
+    };
+    // rotations and scalings use the pivot of the drag tools
+    let entry_apply = |transform: fn(&TransformEntry, &StaticPoint2d) -> Affine| {
+        Callback::from(enclose! {(scene, editor, entry) move |_| {
+            let center = editor.center(&scene, entry.shape);
+            let command = Command::Transform {
+                shape: entry.shape,
+                transform: transform(&entry, &center),
+            };
+            if let Some(next_scene) = scene.apply(&command) {
+                scene.set(next_scene);
+            }
+        }})
+    };
+    let on_preset_change = Callback::from(enclose! {(scene) move |event: Event| {
+        let name = event.target_unchecked_into::<HtmlSelectElement>().value();
+        if let Some(next_scene) =
+            Preset::find(&name).and_then(|preset| scene.apply(&Command::LoadPreset(preset)))
+        {
+            scene.set(next_scene);
+        }
+    }});
+
+    // the result lags behind while the worker is busy, a failing cut shows an empty one
+    let computed_scene = computed.scene.unwrap_or(*scene);
+    let (result, failure) = match &computed.result {
+        Some(Ok(result)) => (result.clone(), None),
+        Some(Err(failure)) => (Default::default(), Some(failure.clone())),
+        None => (Default::default(), None),
+    };
+    use_effect_with_deps(
+        move |failure| {
+            if let (Some(failure), Some(toaster)) = (failure, toaster) {
+                toaster.toast(failure_toast("Cut failed", failure));
+            }
+        },
+        failure.clone(),
+    );
+    let on_copy_test = Callback::from(enclose! {(result) move |_| {
+        copy_to_clipboard(&cut_test(&computed_scene, &result));
+    }});
+    let counts = match &failure {
+        Some(failure) => format!("cut failed: {}", failure.message),
+        None => format!(
+            "inside: {} ({:.2}), outside: {} ({:.2})",
+            result.inside.len(),
+            result.inside_area(),
+            result.outside.len(),
+            result.outside_area()
+        ),
+    };
+    let expected = scene
+        .preset
+        .map(|preset| {
+            let expected = preset.expected;
+            format!(
+                "expected inside: {} ({:.2}), outside: {} ({:.2})",
+                expected.inside_triangles,
+                expected.inside_area,
+                expected.outside_triangles,
+                expected.outside_area
+            )
+        })
+        .unwrap_or_default();
+    // dragging the swept corner keeps the heat map, anything else makes it stale
+    let heat_map = swept
+        .sweep
+        .as_deref()
+        .filter(|sweep| *show_heat_map && sweep.applies_to(&scene));
+    let sweep_text = match swept.sweep.as_deref() {
+        Some(sweep) if sweep.applies_to(&scene) => {
+            let counts = sweep.counts();
+            format!(
+                "swept p{}: {} of {} cells{}, panics: {}, area mismatches: {}, degenerate: {}",
+                sweep.corner + 1,
+                sweep.cells.len(),
+                sweep.cell_count(),
+                if swept.busy { " so far" } else { "" },
+                counts.panicked,
+                counts.area_mismatch,
+                counts.degenerate_output
+            )
+        }
+        Some(_) => "sweep outdated".to_string(),
+        None => String::new(),
+    };
+    let orientation = |shape| TriangleMetrics::of(scene.shape(shape)).orientation;
+    let winding = WindingCounts::of(result.inside.iter().chain(result.outside.iter()));
+    let winding_text = format!(
+        "triangle {}, cutter {}{}",
+        orientation(Shape::Triangle),
+        orientation(Shape::Cutter),
+        if winding.consistent() {
+            String::new()
+        } else {
+            format!(
+                " - output winding inconsistent: {} counter-clockwise, {} clockwise",
+                winding.counter_clockwise, winding.clockwise
+            )
+        }
+    );
+    // about a hundred cuts, so the check only runs on request
+    let invariance = use_worker(
+        *invariance_scene,
+        |scene| Job::Invariance(SceneFile::from(scene)),
+        |answer| match answer {
+            Answer::Invariance(check) => Some(check),
+            _ => None,
+        },
+        WORKER_TIMEOUT_MS,
+    );
+    // any edit makes the check stale, the selected deviation is shown mapped back
+    let invariance_check = match &invariance.output {
+        Some(Ok(check)) if !invariance.busy && check.applies_to(&scene) => Some(check),
+        _ => None,
+    };
+    let invariance_text = match (&invariance.output, invariance_check) {
+        _ if invariance.busy => "checking invariance".to_string(),
+        (_, Some(check)) => format!(
+            "{} of {} variations differ",
+            check.deviations.len(),
+            check.checked
+        ),
+        (Some(Err(message)), None) => format!("invariance check failed: {message}"),
+        (Some(Ok(_)), None) => "invariance check outdated".to_string(),
+        (None, None) => String::new(),
+    };
+    let deviations = invariance_check
+        .map(|check| check.deviations.as_slice())
+        .unwrap_or_default();
+    let deviation_figures: Rc<[Figure]> = deviations
+        .get(*selected_deviation)
+        .map(|deviation| deviation.figures().into())
+        .unwrap_or_else(|| Rc::from([]));
+    // the editor changes with every mouse move, the layers only with the scene and the
+    // answers of the worker, switching a layer off keeps its figures
+    let cut_figures = use_memo(
+        |(scene, result): &(Scene, CutResult)| {
+            let figures: Rc<[Figure]> = scene.display_list(result).into();
+            let annotations: Rc<[Figure]> =
+                scene.layer_figures(SceneLayer::Annotations, result).into();
+            let winding: Rc<[Figure]> = scene.layer_figures(SceneLayer::Winding, result).into();
+            let input: Rc<[Figure]> = scene.layer_figures(SceneLayer::Input, result).into();
+            (figures, annotations, winding, input)
+        },
+        (*scene, result.clone()),
+    );
+    // the generation changes along with the sweep, comparing it is cheaper
+    let swept_sweep = swept.sweep.clone();
+    let heat_map_figures = use_memo(
+        move |_: &u64| -> Rc<[Figure]> { swept_sweep.iter().map(|sweep| sweep.figure()).collect() },
+        swept.generation,
+    );
+    // the reference clip is expensive, it is only computed while shown
+    let precision_shown = *show_precision || *split_view;
+    let precision_report = use_worker(
+        precision_shown.then(|| (computed_scene, result.clone())),
+        |(scene, result)| Job::Precision {
+            scene: SceneFile::from(scene),
+            result: result.clone(),
+        },
+        |answer| match answer {
+            Answer::Precision(report) => Some(report),
+            _ => None,
+        },
+        WORKER_TIMEOUT_MS,
+    );
+    let precision_layer = use_memo(
+        |output: &Option<Result<PrecisionReport, String>>| -> (Rc<[Figure]>, String) {
+            let report = match output {
+                Some(Ok(report)) => report,
+                Some(Err(message)) => {
+                    return (
+                        Rc::from([]),
+                        format!("reference comparison failed: {message}"),
+                    )
+                }
+                None => return (Rc::from([]), String::new()),
+            };
+            let precision = format!(
+                "reference inside: {:.2}, area errors: {:.1e} / {:.1e}, moved points: {} (max {:.1e}), missing points: {}, degenerate: {}, misclassified: {}, appeared: {}, disappeared area: {:.1e}{}",
+                report.exact.area,
+                report.inside_area_error,
+                report.outside_area_error,
+                report.moved_points.len(),
+                report.max_point_error(),
+                report.missing_points.len(),
+                report.degenerate_triangles.len(),
+                report.misclassified_triangles.len(),
+                report.appeared_triangles.len(),
+                report.disappeared_area,
+                if report.beyond_rounding() {
+                    " - beyond rounding"
+                } else {
+                    " - rounding only"
+                }
+            );
+            (precision_figures(report).into(), precision)
+        },
+        precision_report.output.clone(),
+    );
+    let (cut, annotations, winding_figures, input) = (*cut_figures).clone();
+    let (exact, precision) = (*precision_layer).clone();
+    let precision = if precision_shown {
+        precision
+    } else {
+        String::new()
+    };
+    let layers = Layers::default()
+        .with(
+            Layer::new("heat map", -1, (*heat_map_figures).clone())
+                .with_visible(heat_map.is_some()),
+        )
+        .with(Layer::new("cut", 0, cut))
+        .with(Layer::new("annotations", 1, annotations).with_visible(*show_annotations))
+        .with(Layer::new("winding", 1, winding_figures).with_visible(*show_winding))
+        .with(Layer::new("exact", 2, exact.clone()).with_visible(*show_precision))
+        .with(
+            Layer::new("invariance", 3, deviation_figures.clone())
+                .with_visible(!deviations.is_empty()),
+        )
+        .with(Layer::overlay("measure", 4, measurer.figures()).with_visible(*measuring));
+    let overlay: PolygonList = if *measuring {
+        PolygonList::default()
+    } else {
+        editor.overlay(&scene).into()
+    };
+    let measurement = match measurer.measurement() {
+        Some(measurement) if *measuring => measurement.to_string(),
+        _ => String::new(),
+    };
+    // the heat map cells stay put while the corner moves over them
+    let projection = ProjectionConfig {
+        world_bounds: heat_map.map(|sweep| sweep.bounds.clone()),
+        ..ProjectionConfig::default()
+    };
+
+    // the split view puts the stages of the cut next to each other, each on the outlines
+    // of the input
+    let input_layer = Layer::new("input", 0, input);
+    let mut panels = vec![
+        Panel::new("Input", Layers::default().with(input_layer.clone())),
+        Panel::new("Cut", layers.clone()),
+        Panel::new(
+            "Reference clip",
+            Layers::default()
+                .with(input_layer.clone())
+                .with(Layer::new("exact", 2, exact)),
+        ),
+    ];
+    if let Some(deviation) = deviations.get(*selected_deviation) {
+        panels.push(Panel::new(
+            format!("Mapped back, {}", deviation.variation),
+            Layers::default().with(input_layer).with(Layer::new(
+                "invariance",
+                3,
+                deviation_figures,
+            )),
+        ));
+    }
+
+    let selected_preset = scene.preset.map(|preset| preset.name);
+    let modes = editor.deref().snap_modes;
+    html! {
+        <>
+            <Toolbar>
+                <ToolbarItem>
+                    <select onchange={on_preset_change}>
+                        <option selected={selected_preset.is_none()} disabled=true>{"Custom"}</option>
+                        { for PRESETS.iter().map(|preset| html! {
+                            <option
+                                value={preset.name}
+                                selected={selected_preset == Some(preset.name)}
+                            >
+                                {preset.name}
+                            </option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Annotations" checked={*show_annotations} onchange={on_annotations_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Split view" checked={*split_view} onchange={on_split_view_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Reference comparison" checked={*show_precision} onchange={on_precision_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button disabled={failure.is_some()} onclick={on_copy_test}>{"Copy as test"}</button>
+                </ToolbarItem>
+                <ToolbarItem>{counts}</ToolbarItem>
+                <ToolbarItem>
+                    if computed.busy {
+                        <Spinner size={SpinnerSize::Md}/>
+                    }
+                </ToolbarItem>
+                <ToolbarItem>{expected}</ToolbarItem>
+                <ToolbarItem>{precision}</ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <Switch label="Winding" checked={*show_winding} onchange={on_winding_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button onclick={flip(Shape::Triangle)}>{"Flip triangle"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button onclick={flip(Shape::Cutter)}>{"Flip cutter"}</button>
+                </ToolbarItem>
+                <ToolbarItem>{winding_text}</ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <Switch label="Snap to grid" checked={modes.grid} onchange={snap_toggle(|m, v| m.grid = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Integers" checked={modes.integer} onchange={snap_toggle(|m, v| m.integer = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Vertices" checked={modes.vertices} onchange={snap_toggle(|m, v| m.vertices = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Edges" checked={modes.edges} onchange={snap_toggle(|m, v| m.edges = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Midpoints" checked={modes.midpoints} onchange={snap_toggle(|m, v| m.midpoints = v)}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Intersections" checked={modes.intersections} onchange={snap_toggle(|m, v| m.intersections = v)}/>
+                </ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <select onchange={on_sweep_corner_change}>
+                        { for ["p1", "p2", "p3"].iter().enumerate().map(|(corner, name)| html! {
+                            <option selected={corner == *sweep_corner}>{name}</option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button onclick={on_run_sweep}>{"Run sweep"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <Switch label="Heat map" checked={*show_heat_map} onchange={on_heat_map_change}/>
+                </ToolbarItem>
+                <ToolbarItem>{sweep_text}</ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <button onclick={on_check_invariance}>{"Check invariance"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <select disabled={deviations.is_empty()} onchange={on_deviation_change}>
+                        { for deviations.iter().enumerate().map(|(idx, deviation)| html! {
+                            <option selected={idx == *selected_deviation}>{deviation.to_string()}</option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>{invariance_text}</ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <select onchange={on_edit_tool_change}>
+                        { for EDIT_TOOLS.iter().map(|(tool, name)| html! {
+                            <option selected={*tool == editor.tool}>{name}</option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <button disabled={editor.pivot().is_none()} onclick={on_reset_pivot}>{"Pivot at centroid"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <select onchange={on_entry_shape_change}>
+                        { for SHAPES.iter().map(|(shape, name)| html! {
+                            <option selected={*shape == entry.shape}>{name}</option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <input type="number" step="any" value={entry.degrees.to_string()} onchange={entry_input(|e, v| e.degrees = v)}/>
+                    <button onclick={entry_apply(|e, center| Affine::rotation(center, e.degrees))}>{"Rotate by °"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <input type="number" step="any" value={entry.factor.to_string()} onchange={entry_input(|e, v| e.factor = v)}/>
+                    <button onclick={entry_apply(|e, center| Affine::scaling(center, e.factor))}>{"Scale by"}</button>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <input type="number" step="any" value={entry.dx.to_string()} onchange={entry_input(|e, v| e.dx = v)}/>
+                    <input type="number" step="any" value={entry.dy.to_string()} onchange={entry_input(|e, v| e.dy = v)}/>
+                    <button onclick={entry_apply(|e, _| Affine::translation(e.dx, e.dy))}>{"Translate by"}</button>
+                </ToolbarItem>
+            </Toolbar>
+            <Toolbar>
+                <ToolbarItem>
+                    <Switch label="Measure" checked={*measuring} onchange={on_measuring_change}/>
+                </ToolbarItem>
+                <ToolbarItem>
+                    <select disabled={!*measuring} onchange={on_measure_tool_change}>
+                        { for MEASURE_TOOLS.iter().map(|(tool, name)| html! {
+                            <option selected={*tool == measurer.tool}>{name}</option>
+                        })}
+                    </select>
+                </ToolbarItem>
+                <ToolbarItem>{measurement}</ToolbarItem>
+            </Toolbar>
+            if *split_view {
+                <SplitView {panels} {overlay} {projection} {on_mouse_event}/>
+            } else {
+                <Render2d {layers} {overlay} {projection} {on_mouse_event}/>
+            }
+        </>
+    }
 }
@@ -0,0 +1,75 @@
+//! Screen space layout of annotation figures, shared by the canvas and svg renderers
+
+/// Pixel offset of a label from its anchor point
+pub const LABEL_OFFSET: (f64, f64) = (8.0, -8.0);
+
+const ARROW_HEAD_LENGTH: f64 = 10.0;
+const ARROW_HEAD_ANGLE: f64 = 0.45;
+const DIMENSION_DISTANCE: f64 = 12.0;
+
+type Line = ((f64, f64), (f64, f64));
+
+/// The two strokes of an arrow head pointing to `to`
+pub fn arrow_head(from: (f64, f64), to: (f64, f64)) -> [Line; 2] {
+    let angle = (from.1 - to.1).atan2(from.0 - to.0);
+    [-ARROW_HEAD_ANGLE, ARROW_HEAD_ANGLE].map(|delta| {
+        let (sin, cos) = (angle + delta).sin_cos();
+        (
+            to,
+            (
+                to.0 + cos * ARROW_HEAD_LENGTH,
+                to.1 + sin * ARROW_HEAD_LENGTH,
+            ),
+        )
+    })
+}
+
+/// Strokes and label position of a dimension line between two screen points
+pub struct DimensionLayout {
+    pub lines: Vec<Line>,
+    /// Center of the length label
+    pub label: (f64, f64),
+}
+
+pub fn dimension_layout(from: (f64, f64), to: (f64, f64)) -> DimensionLayout {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx.hypot(dy);
+    let (nx, ny) = if length > 0.0 {
+        (-dy / length, dx / length)
+    } else {
+        (0.0, -1.0)
+    };
+    let shift = |(x, y): (f64, f64), distance: f64| (x + nx * distance, y + ny * distance);
+    let (start, end) = (
+        shift(from, DIMENSION_DISTANCE),
+        shift(to, DIMENSION_DISTANCE),
+    );
+    let mut lines = vec![
+        (shift(from, 3.0), shift(from, DIMENSION_DISTANCE + 4.0)),
+        (shift(to, 3.0), shift(to, DIMENSION_DISTANCE + 4.0)),
+        (start, end),
+    ];
+    lines.extend(arrow_head(end, start));
+    lines.extend(arrow_head(start, end));
+    DimensionLayout {
+        lines,
+        label: shift(
+            ((from.0 + to.0) / 2.0, (from.1 + to.1) / 2.0),
+            DIMENSION_DISTANCE * 2.0,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::render::annotation::arrow_head;
+
+    #[test]
+    fn test_arrow_head() {
+        let [(tip1, (x1, y1)), (tip2, (x2, y2))] = arrow_head((0.0, 0.0), (100.0, 0.0));
+        assert_eq!(tip1, (100.0, 0.0));
+        assert_eq!(tip2, (100.0, 0.0));
+        assert!(x1 < 100.0 && x2 < 100.0);
+        assert!((y1 + y2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,155 @@
+use num_traits::Pow;
+use triangles::prelude::{BoundingBoxValues, StaticPoint2d};
+
+use crate::render::projection::ScreenProject2d;
+use crate::render::tick_sequence::TickSequence;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// Tick label in screen coordinates, `y` is the text baseline
+#[derive(Clone, PartialEq, Debug)]
+pub struct AxisLabel {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+    pub align: TextAlign,
+}
+
+/// Axis lines, tick marks and tick labels in screen coordinates
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AxisLayout {
+    pub lines: Vec<((f64, f64), (f64, f64))>,
+    pub labels: Vec<AxisLabel>,
+    /// Lines through the ticks across the whole view
+    pub grid: Vec<((f64, f64), (f64, f64))>,
+}
+
+#[derive(Debug)]
+enum TickSideHorizontal {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+enum TickSideVertical {
+    Top,
+    Bottom,
+}
+
+/// Places the axes through the origin, or along the canvas border if the origin is not visible
+pub fn layout_axes(
+    p: &ScreenProject2d,
+    bbox: &BoundingBoxValues,
+    width: f64,
+    height: f64,
+) -> AxisLayout {
+    let mut layout = AxisLayout::default();
+    let (zero_x, zero_y) = p.project_point::<StaticPoint2d>(&(0.0, 0.0).into());
+    let (min_x, min_y) = p.project_point::<StaticPoint2d>(&(bbox.min_x(), bbox.min_y()).into());
+    let (max_x, max_y) = p.project_point::<StaticPoint2d>(&(bbox.max_x(), bbox.max_y()).into());
+    let (tick_y, tick_side_vertical) = if zero_y < 0.0 {
+        (0.0, TickSideVertical::Bottom)
+    } else if zero_y > height {
+        (height, TickSideVertical::Top)
+    } else {
+        layout.lines.push(((min_x, zero_y), (max_x, zero_y)));
+        (
+            zero_y,
+            if zero_y > height / 2.0 {
+                TickSideVertical::Top
+            } else {
+                TickSideVertical::Bottom
+            },
+        )
+    };
+
+    let (tick_x, tick_side_horizontal) = if zero_x < 0.0 {
+        (min_x, TickSideHorizontal::Right)
+    } else if zero_x > width {
+        (max_x, TickSideHorizontal::Left)
+    } else {
+        layout.lines.push(((zero_x, min_y), (zero_x, max_y)));
+        (
+            zero_x,
+            if zero_x > width / 2.0 {
+                TickSideHorizontal::Left
+            } else {
+                TickSideHorizontal::Right
+            },
+        )
+    };
+
+    let (x_step, y_step) = grid_steps(p);
+    for y_tick in TickSequence::new(bbox.min_y().0, bbox.max_y().0, y_step).iter() {
+        let (_, y) = p.project_point::<StaticPoint2d>(&(0.0, y_tick).into());
+        let text = format!("{}", y_tick);
+        layout.grid.push(((min_x, y), (max_x, y)));
+        match tick_side_horizontal {
+            TickSideHorizontal::Right => {
+                layout.lines.push(((tick_x, y), (tick_x + 5.0, y)));
+                layout.labels.push(AxisLabel {
+                    x: tick_x + 10.0,
+                    y,
+                    text,
+                    align: TextAlign::Left,
+                });
+            }
+            TickSideHorizontal::Left => {
+                layout.lines.push(((tick_x, y), (tick_x - 5.0, y)));
+                layout.labels.push(AxisLabel {
+                    x: tick_x - 10.0,
+                    y,
+                    text,
+                    align: TextAlign::Right,
+                });
+            }
+        }
+    }
+
+    for x_tick in TickSequence::new(bbox.min_x().0, bbox.max_x().0, x_step).iter() {
+        let (x, _) = p.project_point::<StaticPoint2d>(&(x_tick, 0.0).into());
+        let text = format!("{}", x_tick);
+        layout.grid.push(((x, min_y), (x, max_y)));
+        let (tick_end, label_y) = match tick_side_vertical {
+            TickSideVertical::Top => (tick_y - 5.0, tick_y - 10.0),
+            TickSideVertical::Bottom => (tick_y + 5.0, tick_y + 20.0),
+        };
+        layout.lines.push(((x, tick_y), (x, tick_end)));
+        layout.labels.push(AxisLabel {
+            x,
+            y: label_y,
+            text,
+            align: TextAlign::Center,
+        });
+    }
+    layout
+}
+
+/// World distance between two grid ticks on the x and y axis
+pub fn grid_steps(p: &ScreenProject2d) -> (f64, f64) {
+    (
+        find_optimal_step(40.0 / p.x_scale().0),
+        find_optimal_step(40.0 / p.y_scale().0),
+    )
+}
+
+fn find_optimal_step(step: f64) -> f64 {
+    let log10 = step.log10();
+    let floor = log10.floor();
+    let fract = log10 - floor;
+    let scale = if fract < 0.17 {
+        1.0
+    } else if fract < 0.5 {
+        2.0
+    } else if fract < 0.85 {
+        5.0
+    } else {
+        10.0
+    };
+    10.0.pow(floor) * scale
+}
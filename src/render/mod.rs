@@ -0,0 +1,6 @@
+pub mod annotation;
+pub mod axes;
+pub mod projection;
+pub mod svg;
+pub mod theme;
+pub mod tick_sequence;
@@ -0,0 +1,335 @@
+use triangles::prelude::{BoundingBox, BoundingBoxValues, Number, Point2d, StaticPoint2d};
+
+/// Space kept free around the displayed world region
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Margin {
+    /// Grow the world region by this fraction before fitting it into the canvas
+    Relative(f64),
+    /// Keep this many pixels free on every side of the canvas
+    Pixels(f64),
+}
+
+/// How the world region is scaled into the canvas
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AspectMode {
+    /// Same scale on both axes, the world region is centered
+    Equal,
+    /// Fill the canvas, each axis gets its own scale
+    Stretch,
+    /// Equal scale unless the two axis scales would differ by more than the given factor
+    StretchAbove(f64),
+}
+
+/// Direction the world y axis points to on the screen
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum YAxis {
+    #[default]
+    Up,
+    Down,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProjectionConfig {
+    pub margin: Margin,
+    pub aspect: AspectMode,
+    pub y_axis: YAxis,
+    /// Show exactly this world region instead of fitting to the displayed figures
+    pub world_bounds: Option<BoundingBoxValues>,
+}
+
+impl Default for ProjectionConfig {
+    fn default() -> Self {
+        Self {
+            margin: Margin::Relative(0.1),
+            aspect: AspectMode::Equal,
+            y_axis: YAxis::Up,
+            world_bounds: None,
+        }
+    }
+}
+
+impl ProjectionConfig {
+    /// Finds the projection for the given content and returns it with the world region it shows.
+    ///
+    /// Returns `None` if there is neither content nor explicit world bounds.
+    pub fn fit(
+        &self,
+        content: &BoundingBox,
+        canvas_width: f64,
+        canvas_height: f64,
+    ) -> Option<(ScreenProject2d, BoundingBoxValues)> {
+        let bbox = match (&self.world_bounds, content) {
+            (Some(bounds), _) => bounds.clone(),
+            (None, BoundingBox::Box(bbox)) => bbox.clone(),
+            (None, BoundingBox::Empty) => return None,
+        };
+        let (bbox, pixel_margin) = match self.margin {
+            Margin::Relative(factor) => (bbox.expand(factor.into()), 0.0),
+            Margin::Pixels(pixels) => (bbox, pixels.max(0.0)),
+        };
+        let projection = ScreenProject2d::fit(
+            &bbox,
+            canvas_width,
+            canvas_height,
+            pixel_margin,
+            self.aspect,
+            self.y_axis,
+        );
+        Some((projection, bbox))
+    }
+
+    /// Config of views which show the same world region, the one around all of their
+    /// `contents` unless `world_bounds` is set already
+    pub fn shared(&self, contents: impl IntoIterator<Item = BoundingBox>) -> Self {
+        if self.world_bounds.is_some() {
+            return self.clone();
+        }
+        let mut union = BoundingBox::default();
+        for content in contents {
+            union += content;
+        }
+        Self {
+            world_bounds: match union {
+                BoundingBox::Box(bounds) => Some(bounds),
+                BoundingBox::Empty => None,
+            },
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct ScreenProject2d {
+    x_scale: Number,
+    y_scale: Number,
+    x_offset: Number,
+    y_offset: Number,
+}
+
+impl ScreenProject2d {
+    /// Equal scale on both axes, y axis up and no margin
+    pub fn from_bounding_box(
+        bbox: &BoundingBoxValues,
+        canvas_width: f64,
+        canvas_height: f64,
+    ) -> Self {
+        Self::fit(
+            bbox,
+            canvas_width,
+            canvas_height,
+            0.0,
+            AspectMode::Equal,
+            YAxis::Up,
+        )
+    }
+    fn fit(
+        bbox: &BoundingBoxValues,
+        canvas_width: f64,
+        canvas_height: f64,
+        pixel_margin: f64,
+        aspect: AspectMode,
+        y_axis: YAxis,
+    ) -> Self {
+        let world_width = non_degenerate_extent(bbox.width().0, bbox.height().0);
+        let world_height = non_degenerate_extent(bbox.height().0, bbox.width().0);
+        let available_width = (canvas_width - 2.0 * pixel_margin).max(1.0);
+        let available_height = (canvas_height - 2.0 * pixel_margin).max(1.0);
+
+        let x_fact = available_width / world_width;
+        let y_fact = available_height / world_height;
+        let equal_fact = f64::min(x_fact, y_fact);
+        let (x_fact, y_fact) = match aspect {
+            AspectMode::Equal => (equal_fact, equal_fact),
+            AspectMode::Stretch => (x_fact, y_fact),
+            AspectMode::StretchAbove(ratio) => {
+                if f64::max(x_fact, y_fact) / equal_fact > ratio {
+                    (x_fact, y_fact)
+                } else {
+                    (equal_fact, equal_fact)
+                }
+            }
+        };
+
+        let x_offset = -bbox.min_x().0 * x_fact + (canvas_width - world_width * x_fact) / 2.0;
+        let y_padding = (canvas_height - world_height * y_fact) / 2.0;
+        let (y_scale, y_offset) = match y_axis {
+            YAxis::Up => (-y_fact, bbox.max_y().0 * y_fact + y_padding),
+            YAxis::Down => (y_fact, -bbox.min_y().0 * y_fact + y_padding),
+        };
+        Self {
+            x_scale: x_fact.into(),
+            y_scale: y_scale.into(),
+            x_offset: x_offset.into(),
+            y_offset: y_offset.into(),
+        }
+    }
+    pub fn project_point<P: Point2d>(&self, p: &P) -> (f64, f64) {
+        let StaticPoint2d { x, y } = p.coordinates();
+        (
+            (self.x_scale * x + self.x_offset).into(),
+            (self.y_scale * y + self.y_offset).into(),
+        )
+    }
+    /// Inverse of [`ScreenProject2d::project_point`]
+    pub fn find_origin_point(&self, x: f64, y: f64) -> (Number, Number) {
+        (
+            ((Number::from(x) - self.x_offset) / self.x_scale),
+            ((Number::from(y) - self.y_offset) / self.y_scale),
+        )
+    }
+
+    /// Pixels per world unit along the x axis
+    pub fn x_scale(&self) -> Number {
+        self.x_scale
+    }
+    /// Pixels per world unit along the y axis
+    pub fn y_scale(&self) -> Number {
+        Number::from(self.y_scale.0.abs())
+    }
+    /// Pixels per world unit along the coarser axis
+    pub fn scale(&self) -> Number {
+        Number::from(f64::min(self.x_scale.0, self.y_scale.0.abs()))
+    }
+    pub fn y_axis(&self) -> YAxis {
+        if self.y_scale.0 < 0.0 {
+            YAxis::Up
+        } else {
+            YAxis::Down
+        }
+    }
+}
+
+fn non_degenerate_extent(extent: f64, other: f64) -> f64 {
+    if extent > 0.0 {
+        extent
+    } else if other > 0.0 {
+        other
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+    use triangles::prelude::{BoundingBox, BoundingBoxValues, StaticPoint2d};
+
+    use crate::render::projection::{AspectMode, Margin, ProjectionConfig, ScreenProject2d, YAxis};
+
+    #[test]
+    fn test_projection() {
+        let b = BoundingBoxValues::new((-10.0).into(), (-30.0).into(), 100.0.into(), 150.0.into());
+        let p = ScreenProject2d::from_bounding_box(&b, 65.0, 90.0);
+        let (x, y) = p.project_point::<StaticPoint2d>(&(42.0, 23.0).into());
+        assert_eq!(x, 31.0);
+        assert_eq!(y, 63.5);
+        let (x, y) = p.find_origin_point(x, y);
+        assert_eq!(x.0, 42.0);
+        assert_eq!(y.0, 23.0);
+    }
+
+    #[test]
+    fn test_pixel_margin_and_y_down() {
+        let b = BoundingBoxValues::new(0.0.into(), 0.0.into(), 10.0.into(), 10.0.into());
+        let config = ProjectionConfig {
+            margin: Margin::Pixels(10.0),
+            aspect: AspectMode::Equal,
+            y_axis: YAxis::Down,
+            world_bounds: Some(b),
+        };
+        let (p, _) = config.fit(&BoundingBox::Empty, 120.0, 120.0).unwrap();
+        assert_eq!(
+            p.project_point::<StaticPoint2d>(&(0.0, 0.0).into()),
+            (10.0, 10.0)
+        );
+        assert_eq!(
+            p.project_point::<StaticPoint2d>(&(10.0, 10.0).into()),
+            (110.0, 110.0)
+        );
+        assert_eq!(p.y_axis(), YAxis::Down);
+    }
+
+    #[test]
+    fn test_stretch() {
+        let b = BoundingBoxValues::new(0.0.into(), 0.0.into(), 1000.0.into(), 1.0.into());
+        let stretched = ScreenProject2d::fit(&b, 100.0, 100.0, 0.0, AspectMode::Stretch, YAxis::Up);
+        assert_eq!(stretched.x_scale().0, 0.1);
+        assert_eq!(stretched.y_scale().0, 100.0);
+        let limited = ScreenProject2d::fit(
+            &b,
+            100.0,
+            100.0,
+            0.0,
+            AspectMode::StretchAbove(2000.0),
+            YAxis::Up,
+        );
+        assert_eq!(limited.y_scale().0, 0.1);
+    }
+
+    #[test]
+    fn test_shared() {
+        let pt = |x: f64, y: f64| -> StaticPoint2d { (x, y).into() };
+        let mut left = BoundingBox::default();
+        left += pt(0.0, 0.0);
+        left += pt(1.0, 1.0);
+        let mut right = BoundingBox::default();
+        right += pt(5.0, -2.0);
+        let shared =
+            ProjectionConfig::default().shared([left.clone(), BoundingBox::Empty, right.clone()]);
+        assert_eq!(
+            shared.world_bounds,
+            Some(BoundingBoxValues::new(
+                0.0.into(),
+                (-2.0).into(),
+                5.0.into(),
+                1.0.into()
+            ))
+        );
+        // every view gets the same projection, whatever it shows itself
+        assert_eq!(
+            shared.fit(&BoundingBox::Empty, 100.0, 50.0),
+            shared.fit(&left, 100.0, 50.0)
+        );
+        assert_eq!(shared.shared([right]), shared);
+        assert_eq!(ProjectionConfig::default().shared([]).world_bounds, None);
+    }
+
+    fn assert_round_trip(p: &ScreenProject2d, x: f64, y: f64) {
+        let (sx, sy) = p.project_point::<StaticPoint2d>(&(x, y).into());
+        let (ox, oy) = p.find_origin_point(sx, sy);
+        let tolerance = 1e-9 * (1.0 + x.abs().max(y.abs()));
+        assert!((ox.0 - x).abs() <= tolerance, "x: {x} -> {sx} -> {}", ox.0);
+        assert!((oy.0 - y).abs() <= tolerance, "y: {y} -> {sy} -> {}", oy.0);
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            min_x in -1e4..1e4f64,
+            min_y in -1e4..1e4f64,
+            width in 1e-3..1e4f64,
+            height in 1e-3..1e4f64,
+            canvas_width in 10.0..4000.0f64,
+            canvas_height in 10.0..4000.0f64,
+            margin in 0.0..5.0f64,
+            stretch in any::<bool>(),
+            y_down in any::<bool>(),
+            fx in 0.0..1.0f64,
+            fy in 0.0..1.0f64,
+        ) {
+            let config = ProjectionConfig {
+                margin: Margin::Pixels(margin),
+                aspect: if stretch { AspectMode::Stretch } else { AspectMode::Equal },
+                y_axis: if y_down { YAxis::Down } else { YAxis::Up },
+                world_bounds: Some(BoundingBoxValues::new(
+                    min_x.into(),
+                    min_y.into(),
+                    (min_x + width).into(),
+                    (min_y + height).into(),
+                )),
+            };
+            let (p, _) = config.fit(&BoundingBox::Empty, canvas_width, canvas_height).unwrap();
+            assert_round_trip(&p, min_x + fx * width, min_y + fy * height);
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+use triangles::prelude::{BoundingBox, Polygon2d, StaticPoint2d};
+
+use crate::display::{arc_sweep, AnyGeometry, Figure, HEAT_MAP_OPACITY};
+use crate::render::annotation::{arrow_head, dimension_layout, LABEL_OFFSET};
+use crate::render::axes::{layout_axes, TextAlign};
+use crate::render::projection::{ProjectionConfig, ScreenProject2d, YAxis};
+use crate::render::theme::{Palette, Theme};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SvgOptions {
+    pub width: f64,
+    pub height: f64,
+    pub axes: bool,
+    pub projection: ProjectionConfig,
+    pub theme: Theme,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            height: 600.0,
+            axes: true,
+            projection: ProjectionConfig::default(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+/// Renders the display list the same way `Render2d` draws it on a canvas
+pub fn render_svg(figures: &[Figure], options: &SvgOptions) -> String {
+    let SvgOptions {
+        width,
+        height,
+        theme,
+        ..
+    } = *options;
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" style="font: {}">"#,
+        theme.label_font
+    )
+    .unwrap();
+    writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        theme.background
+    )
+    .unwrap();
+
+    let mut bbox = BoundingBox::default();
+    for figure in figures {
+        bbox += figure.bbox();
+    }
+    if let Some((p, bbox)) = options.projection.fit(&bbox, width, height) {
+        if options.axes {
+            let axes = layout_axes(&p, &bbox, width, height);
+            if let Some(grid) = theme.grid {
+                write_lines(&mut out, axes.grid, grid);
+            }
+            for ((x1, y1), (x2, y2)) in axes.lines {
+                writeln!(
+                    out,
+                    r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}"/>"#,
+                    theme.axis
+                )
+                .unwrap();
+            }
+            for label in axes.labels {
+                let anchor = match label.align {
+                    TextAlign::Left => "start",
+                    TextAlign::Center => "middle",
+                    TextAlign::Right => "end",
+                };
+                writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" fill="{}" text-anchor="{anchor}">{}</text>"#,
+                    label.x, label.y, theme.axis, label.text
+                )
+                .unwrap();
+            }
+        }
+        for figure in figures {
+            write_figure(&mut out, figure, &p, &theme.palette);
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+fn write_figure(out: &mut String, figure: &Figure, p: &ScreenProject2d, palette: &Palette) {
+    let color = palette.css_value(figure.style());
+    match figure.geometry() {
+        AnyGeometry::Polygon(polygon) => {
+            writeln!(
+                out,
+                r#"<polygon points="{}" fill="none" stroke="{color}"/>"#,
+                points(polygon.points(), p)
+            )
+            .unwrap();
+        }
+        AnyGeometry::Lines(lines) => {
+            writeln!(
+                out,
+                r#"<polyline points="{}" fill="none" stroke="{color}"/>"#,
+                points(lines.iter(), p)
+            )
+            .unwrap();
+        }
+        AnyGeometry::HoverMarker(pt) => {
+            let (x, y) = p.project_point(pt);
+            writeln!(
+                out,
+                r#"<circle cx="{x}" cy="{y}" r="5" fill="{color}" stroke="{color}"/>"#
+            )
+            .unwrap();
+        }
+        AnyGeometry::SnapMarker(pt) => {
+            let (x, y) = p.project_point(pt);
+            writeln!(
+                out,
+                r#"<path d="M{} {}h14v14h-14zM{} {}h20M{} {}v20" fill="none" stroke="{color}"/>"#,
+                x - 7.0,
+                y - 7.0,
+                x - 10.0,
+                y,
+                x,
+                y - 10.0
+            )
+            .unwrap();
+        }
+        AnyGeometry::Label { anchor, text } => {
+            let (x, y) = p.project_point(anchor);
+            let (dx, dy) = LABEL_OFFSET;
+            writeln!(
+                out,
+                r#"<text x="{}" y="{}" fill="{color}">{}</text>"#,
+                x + dx,
+                y + dy,
+                escape(text)
+            )
+            .unwrap();
+        }
+        AnyGeometry::Arrow { from, to } => {
+            let (from, to) = (p.project_point(from), p.project_point(to));
+            write_lines(
+                out,
+                [(from, to)].into_iter().chain(arrow_head(from, to)),
+                color,
+            );
+        }
+        AnyGeometry::Dimension { from, to } => {
+            let layout = dimension_layout(p.project_point(from), p.project_point(to));
+            write_lines(out, layout.lines, color);
+            let (x, y) = layout.label;
+            writeln!(
+                out,
+                r#"<text x="{x}" y="{y}" fill="{color}" text-anchor="middle">{}</text>"#,
+                AnyGeometry::dimension_text(from, to)
+            )
+            .unwrap();
+        }
+        AnyGeometry::Circle { center, radius } => {
+            let (x, y) = p.project_point(center);
+            let (rx, ry) = (radius * p.x_scale().0, radius * p.y_scale().0);
+            writeln!(
+                out,
+                r#"<ellipse cx="{x}" cy="{y}" rx="{rx}" ry="{ry}" fill="none" stroke="{color}"/>"#
+            )
+            .unwrap();
+        }
+        AnyGeometry::Arc {
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        } => {
+            let sweep = arc_sweep(*start_angle, *end_angle);
+            let point_at = |angle: f64| {
+                let (sin, cos) = angle.sin_cos();
+                p.project_point(&StaticPoint2d::from((
+                    center.x.0 + radius * cos,
+                    center.y.0 + radius * sin,
+                )))
+            };
+            let ((x1, y1), (x2, y2)) = (point_at(*start_angle), point_at(start_angle + sweep));
+            let (rx, ry) = (radius * p.x_scale().0, radius * p.y_scale().0);
+            let large_arc = u8::from(sweep > PI);
+            // svg sweeps clockwise on screen for flag 1, counter-clockwise in world
+            // coordinates is clockwise on screen only if the y axis points down
+            let sweep_flag = u8::from(p.y_axis() == YAxis::Down);
+            writeln!(
+                out,
+                r#"<path d="M{x1} {y1}A{rx} {ry} 0 {large_arc} {sweep_flag} {x2} {y2}" fill="none" stroke="{color}"/>"#
+            )
+            .unwrap();
+        }
+        AnyGeometry::Points { points, size } => {
+            let path: String = points
+                .iter()
+                .map(|pt| {
+                    let (x, y) = p.project_point(pt);
+                    format!(
+                        "M{} {}h{size}v{size}h-{size}z",
+                        x - size / 2.0,
+                        y - size / 2.0
+                    )
+                })
+                .collect();
+            writeln!(out, r#"<path d="{path}" fill="{color}"/>"#).unwrap();
+        }
+        AnyGeometry::HeatMap { .. } => {
+            for (x, y, width, height, value) in figure.geometry().heat_map_cells() {
+                if value <= 0.0 {
+                    continue;
+                }
+                let (x1, y1) = p.project_point::<StaticPoint2d>(&(x, y).into());
+                let (x2, y2) = p.project_point::<StaticPoint2d>(&(x + width, y + height).into());
+                writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{color}" fill-opacity="{}"/>"#,
+                    x1.min(x2),
+                    y1.min(y2),
+                    (x2 - x1).abs(),
+                    (y2 - y1).abs(),
+                    value.min(1.0) * HEAT_MAP_OPACITY
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+fn write_lines(
+    out: &mut String,
+    lines: impl IntoIterator<Item = ((f64, f64), (f64, f64))>,
+    color: &str,
+) {
+    let path: String = lines
+        .into_iter()
+        .map(|((x1, y1), (x2, y2))| format!("M{x1} {y1}L{x2} {y2}"))
+        .collect();
+    writeln!(out, r#"<path d="{path}" fill="none" stroke="{color}"/>"#).unwrap();
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn points<'a>(points: impl Iterator<Item = &'a StaticPoint2d>, p: &ScreenProject2d) -> String {
+    points
+        .map(|pt| {
+            let (x, y) = p.project_point(pt);
+            format!("{x},{y}")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,144 @@
+//! Colors and fonts of the rendered view, shared by the canvas and the svg output
+
+use crate::display::{CssColor, CssStyle};
+
+/// Colors the [`CssColor`] classes of the figures are drawn in
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Palette {
+    pub black: &'static str,
+    pub blue: &'static str,
+    pub green: &'static str,
+    pub red: &'static str,
+}
+
+impl Palette {
+    pub const STANDARD: Palette = Palette {
+        black: "black",
+        blue: "blue",
+        green: "green",
+        red: "red",
+    };
+    pub const STANDARD_DARK: Palette = Palette {
+        black: "#e0e0e0",
+        blue: "#73a5ff",
+        green: "#5ba352",
+        red: "#f0564a",
+    };
+    /// Okabe-Ito colors, the inside (green) and outside (red) pieces of a cut stay apart
+    /// for the common kinds of color blindness
+    pub const COLORBLIND: Palette = Palette {
+        black: "black",
+        blue: "#0072b2",
+        green: "#009e73",
+        red: "#d55e00",
+    };
+    pub const COLORBLIND_DARK: Palette = Palette {
+        black: "#e0e0e0",
+        blue: "#56b4e9",
+        green: "#009e73",
+        red: "#e69f00",
+    };
+
+    pub fn color(&self, color: &CssColor) -> &'static str {
+        match color {
+            CssColor::Black => self.black,
+            CssColor::Blue => self.blue,
+            CssColor::Green => self.green,
+            CssColor::Red => self.red,
+        }
+    }
+
+    /// Value as accepted by the stroke and fill styles of a canvas or svg
+    pub fn css_value(&self, style: &CssStyle) -> &'static str {
+        match style {
+            CssStyle::Color(color) => self.color(color),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    pub background: &'static str,
+    /// Axis lines, tick marks and tick labels
+    pub axis: &'static str,
+    /// Lines through the ticks, `None` draws no grid, which is the default of both
+    /// themes
+    pub grid: Option<&'static str>,
+    /// CSS font shorthand of all text
+    pub label_font: &'static str,
+    pub palette: Palette,
+}
+
+impl Theme {
+    pub const LIGHT: Theme = Theme {
+        background: "white",
+        axis: "black",
+        grid: None,
+        label_font: "10px sans-serif",
+        palette: Palette::STANDARD,
+    };
+    pub const DARK: Theme = Theme {
+        background: "#1b1d21",
+        axis: "#c7c7c7",
+        grid: None,
+        label_font: "10px sans-serif",
+        palette: Palette::STANDARD_DARK,
+    };
+
+    /// Grid color which stays in the background of the light theme
+    pub const LIGHT_GRID: &'static str = "#e8e8e8";
+    pub const DARK_GRID: &'static str = "#33363b";
+
+    pub fn select(dark: bool, colorblind: bool, grid: bool) -> Self {
+        let (theme, palette) = match (dark, colorblind) {
+            (false, false) => (Theme::LIGHT, Palette::STANDARD),
+            (false, true) => (Theme::LIGHT, Palette::COLORBLIND),
+            (true, false) => (Theme::DARK, Palette::STANDARD_DARK),
+            (true, true) => (Theme::DARK, Palette::COLORBLIND_DARK),
+        };
+        let grid_color = if dark {
+            Theme::DARK_GRID
+        } else {
+            Theme::LIGHT_GRID
+        };
+        Theme {
+            palette,
+            grid: grid.then_some(grid_color),
+            ..theme
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::LIGHT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::display::{CssColor, CssStyle};
+    use crate::render::theme::{Palette, Theme};
+
+    #[test]
+    fn test_select() {
+        assert_eq!(Theme::select(false, false, false), Theme::default());
+        assert_eq!(Theme::default().grid, None);
+        let theme = Theme::select(true, true, true);
+        assert_eq!(theme.background, Theme::DARK.background);
+        assert_eq!(theme.grid, Some(Theme::DARK_GRID));
+        assert_eq!(
+            theme.palette.css_value(&CssStyle::Color(CssColor::Green)),
+            Palette::COLORBLIND_DARK.green
+        );
+        // the standard palette keeps the colors of the figures as they were
+        for color in [
+            CssColor::Black,
+            CssColor::Blue,
+            CssColor::Green,
+            CssColor::Red,
+        ] {
+            assert_eq!(Palette::STANDARD.color(&color), color.css_name());
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use yew::{html, Html};
 use yew_nested_router::Target;
 
+use crate::pages::containment_2d::Containment2d;
+use crate::pages::delaunay_2d::Delaunay2d;
+use crate::pages::ear_clipping_2d::EarClipping2d;
+use crate::pages::mesh_cut_2d::MeshCut2d;
 use crate::pages::test3d::Test3d;
 use crate::pages::triangle_cut_2d::TriangleCut2d;
 
@@ -8,12 +12,20 @@ use crate::pages::triangle_cut_2d::TriangleCut2d;
 pub enum AppRoute {
     #[default]
     Basic2d,
+    Containment2d,
+    Delaunay2d,
+    EarClipping2d,
+    MeshCut2d,
     Test3d,
 }
 
 pub fn switch_main(switch: AppRoute) -> Html {
     match switch {
         AppRoute::Basic2d => html! {<TriangleCut2d/>},
+        AppRoute::Containment2d => html! {<Containment2d/>},
+        AppRoute::Delaunay2d => html! {<Delaunay2d/>},
+        AppRoute::EarClipping2d => html! {<EarClipping2d/>},
+        AppRoute::MeshCut2d => html! {<MeshCut2d/>},
         AppRoute::Test3d => {
             html! {<Test3d/>}
         }
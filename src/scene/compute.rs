@@ -0,0 +1,287 @@
+//! Computations run off the main thread by a web worker.
+//!
+//! While a corner is dragged, scenes arrive faster than the worker answers. Only the
+//! latest of them is sent once the running request is answered, the ones in between are
+//! dropped, see [`LatestOnly`]. The web build aborts on panic, which only ends the
+//! worker, the page notices the missing answer and starts a new one. A sweep is answered
+//! cell by cell, so the cell without answer is the one which panicked.
+
+use gloo::worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::cut::CutResult;
+use crate::geometry::exact::PrecisionReport;
+use crate::geometry::robustness::Violations;
+use crate::scene::file::{MeshFile, SceneFile};
+use crate::scene::invariance::InvarianceCheck;
+use crate::scene::mesh::MeshCut;
+use crate::scene::polygon::PolygonScene;
+use crate::scene::sweep::Sweep;
+use crate::scene::Scene;
+
+/// Computation of a page
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Job {
+    Cut(SceneFile),
+    /// Comparison of the cut of a scene with the reference clip
+    Precision {
+        scene: SceneFile,
+        result: CutResult,
+    },
+    Invariance(SceneFile),
+    /// Cells of the sweep from the given index on, each answered on its own
+    Sweep {
+        sweep: Sweep,
+        from: usize,
+    },
+    /// Every triangle of the mesh cut by the polygon
+    Mesh {
+        mesh: MeshFile,
+        cutter: PolygonScene,
+    },
+}
+
+/// Outcome of a [`Job`] of the same kind
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Answer {
+    /// The cut, or the message of the failure
+    Cut(Result<CutResult, String>),
+    Precision(PrecisionReport),
+    Invariance(InvarianceCheck),
+    SweepCell {
+        index: usize,
+        violations: Option<Violations>,
+    },
+    Mesh(MeshCut),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CutRequest {
+    pub id: u64,
+    pub job: Job,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct CutResponse {
+    pub id: u64,
+    pub answer: Answer,
+}
+
+/// Runs the job of `request`, `respond` is called once per answer
+pub fn compute(request: &CutRequest, mut respond: impl FnMut(CutResponse)) {
+    let id = request.id;
+    let answer = match &request.job {
+        Job::Cut(scene) => Answer::Cut(
+            Scene::from(scene)
+                .guarded_cut()
+                .map_err(|failure| failure.message),
+        ),
+        Job::Precision { scene, result } => {
+            Answer::Precision(Scene::from(scene).precision_report(result))
+        }
+        Job::Invariance(scene) => Answer::Invariance(InvarianceCheck::run(&Scene::from(scene))),
+        Job::Sweep { sweep, from } => {
+            for index in *from..sweep.cell_count() {
+                let violations = sweep.check(index);
+                respond(CutResponse {
+                    id,
+                    answer: Answer::SweepCell { index, violations },
+                });
+            }
+            return;
+        }
+        Job::Mesh { mesh, cutter } => Answer::Mesh(MeshCut::run(&mesh.to_triangles(), cutter, now)),
+    };
+    respond(CutResponse { id, answer });
+}
+
+/// Milliseconds on the clock of the worker
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    use wasm_bindgen::JsCast;
+
+    js_sys::global()
+        .unchecked_into::<web_sys::WorkerGlobalScope>()
+        .performance()
+        .map_or(0.0, |performance| performance.now())
+}
+
+/// The worker only runs in the browser, elsewhere nothing is timed
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    0.0
+}
+
+/// Worker answering each [`CutRequest`] with a [`CutResponse`]
+pub struct CutWorker;
+
+impl Worker for CutWorker {
+    type Message = ();
+    type Input = CutRequest;
+    type Output = CutResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+        compute(&msg, |response| scope.respond(id, response));
+    }
+}
+
+/// Outcome of a finished request
+#[derive(Clone, PartialEq, Debug)]
+pub enum Completed<T> {
+    /// Nothing was submitted since, the answer is up to date
+    Latest(T),
+    /// Newer input was submitted meanwhile, the answer is stale and this request is
+    /// to be sent next
+    Superseded(u64, T),
+    /// The request was not running, e.g. because it was given up on
+    Unknown,
+}
+
+/// Keeps at most one request running and only the latest input waiting
+#[derive(Clone, PartialEq, Debug)]
+pub struct LatestOnly<T> {
+    next_id: u64,
+    running: Option<(u64, T)>,
+    pending: Option<T>,
+}
+
+impl<T> Default for LatestOnly<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            running: None,
+            pending: None,
+        }
+    }
+}
+
+impl<T: Clone> LatestOnly<T> {
+    /// Returns the request to send right away, or `None` if another one is running,
+    /// in which case `input` replaces the waiting one
+    pub fn submit(&mut self, input: T) -> Option<(u64, T)> {
+        if self.running.is_some() {
+            self.pending = Some(input);
+            return None;
+        }
+        self.next_id += 1;
+        self.running = Some((self.next_id, input.clone()));
+        Some((self.next_id, input))
+    }
+
+    pub fn complete(&mut self, id: u64) -> Completed<T> {
+        match self.running.take() {
+            Some((running_id, input)) if running_id == id => match self.pending.take() {
+                None => Completed::Latest(input),
+                Some(pending) => {
+                    let (next_id, next) = self
+                        .submit(pending)
+                        .expect("no request is running after take");
+                    Completed::Superseded(next_id, next)
+                }
+            },
+            running => {
+                self.running = running;
+                Completed::Unknown
+            }
+        }
+    }
+
+    pub fn busy(&self) -> bool {
+        self.running.is_some()
+    }
+
+    pub fn running(&self) -> Option<&(u64, T)> {
+        self.running.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scene::compute::{compute, Answer, Completed, CutRequest, Job, LatestOnly};
+    use crate::scene::file::{MeshFile, SceneFile};
+    use crate::scene::mesh::{default_cutter, grid_mesh, MeshCut};
+    use crate::scene::sweep::Sweep;
+    use crate::scene::Scene;
+
+    #[test]
+    fn test_latest_only() {
+        let mut queue = LatestOnly::default();
+        assert_eq!(queue.submit('a'), Some((1, 'a')));
+        assert!(queue.busy());
+        assert_eq!(queue.submit('b'), None);
+        assert_eq!(queue.submit('c'), None);
+        assert_eq!(queue.complete(7), Completed::Unknown);
+        assert_eq!(queue.complete(1), Completed::Superseded(2, 'c'));
+        assert_eq!(queue.running(), Some(&(2, 'c')));
+        assert_eq!(queue.complete(2), Completed::Latest('c'));
+        assert!(!queue.busy());
+    }
+
+    #[test]
+    fn test_compute() {
+        let scene = Scene::default();
+        let request = |id, job| {
+            let mut responses = Vec::new();
+            compute(&CutRequest { id, job }, |response| responses.push(response));
+            responses
+        };
+        let responses = request(3, Job::Cut(SceneFile::from(&scene)));
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].id, 3);
+        assert_eq!(responses[0].answer, Answer::Cut(Ok(scene.cut())));
+
+        let precision = Job::Precision {
+            scene: SceneFile::from(&scene),
+            result: scene.cut(),
+        };
+        assert_eq!(
+            request(4, precision)[0].answer,
+            Answer::Precision(scene.precision_report(&scene.cut()))
+        );
+        let Answer::Invariance(check) =
+            &request(5, Job::Invariance(SceneFile::from(&scene)))[0].answer
+        else {
+            panic!("invariance job answered with another kind");
+        };
+        assert!(check.applies_to(&scene));
+
+        // a sweep resumed after a panic only checks the remaining cells
+        let sweep = Sweep::new(&scene, 2, Sweep::region(&scene).unwrap(), 3);
+        let complete = Sweep::run(&scene, 2, sweep.bounds.clone(), 3);
+        let cells: Vec<Answer> = request(6, Job::Sweep { sweep, from: 7 })
+            .into_iter()
+            .map(|response| response.answer)
+            .collect();
+        assert_eq!(
+            cells,
+            vec![
+                Answer::SweepCell {
+                    index: 7,
+                    violations: complete.cells[7],
+                },
+                Answer::SweepCell {
+                    index: 8,
+                    violations: complete.cells[8],
+                },
+            ]
+        );
+
+        let mesh = grid_mesh(3, 3, 30.0);
+        let job = Job::Mesh {
+            mesh: MeshFile::from(mesh.as_slice()),
+            cutter: default_cutter(),
+        };
+        let answer = request(7, job).remove(0).answer;
+        let expected = MeshCut::run(&mesh, &default_cutter(), || 0.0);
+        assert_eq!(answer, Answer::Mesh(expected));
+        // the mesh cut with its triangulated polygon survives the way to the page
+        let json = serde_json::to_string(&answer).unwrap();
+        assert_eq!(serde_json::from_str::<Answer>(&json).unwrap(), answer);
+    }
+}
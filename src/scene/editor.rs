@@ -0,0 +1,411 @@
+use triangles::prelude::{Point2d, Polygon2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::containment::{classify, Containment};
+use crate::geometry::snap::{SnapModes, SnapTarget, Snapper};
+use crate::geometry::transform::{centroid, Affine};
+use crate::scene::{Command, Scene, Shape};
+
+/// Pointer state in world coordinates
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PointerInput {
+    pub position: StaticPoint2d,
+    pub pressed: bool,
+    /// World units per pixel
+    pub resolution: f64,
+    /// World distance between the displayed grid ticks
+    pub grid_step: (f64, f64),
+    pub snapping_disabled: bool,
+}
+
+/// What dragging on the canvas does
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum EditTool {
+    /// Moves single corners of the cut triangle
+    #[default]
+    Corners,
+    /// Moves the triangle or the cutter as a whole
+    Translate,
+    /// Rotates a shape around the pivot, a click on empty space places the pivot
+    Rotate,
+    /// Scales a shape away from the pivot
+    Scale,
+}
+
+/// A shape being transformed by dragging
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct TransformDrag {
+    shape: Shape,
+    /// The shape when the drag started
+    original: StaticTriangle2d<StaticPoint2d>,
+    start: StaticPoint2d,
+}
+
+/// Interaction state for dragging the corners of the cut triangle and transforming the
+/// triangle and the cutter
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct Editor {
+    pub snap_modes: SnapModes,
+    pub tool: EditTool,
+    selected_corner: Option<usize>,
+    snap: Option<SnapTarget>,
+    /// Center of rotation and scaling, the centroid of the transformed shape if unset
+    pivot: Option<StaticPoint2d>,
+    hovered_shape: Option<Shape>,
+    drag: Option<TransformDrag>,
+    pressed: bool,
+}
+
+/// Pick and snap distance in pixels
+pub(crate) const PICK_RADIUS: f64 = 10.0;
+/// Rotations by dragging snap to multiples of this angle
+pub const ROTATION_SNAP_DEGREES: f64 = 15.0;
+/// Scalings by dragging snap to multiples of this factor
+pub const SCALE_SNAP: f64 = 0.1;
+
+impl Editor {
+    pub fn new(snap_modes: SnapModes) -> Self {
+        Self {
+            snap_modes,
+            ..Default::default()
+        }
+    }
+    pub fn selected_corner(&self) -> Option<usize> {
+        self.selected_corner
+    }
+    pub fn snap(&self) -> Option<SnapTarget> {
+        self.snap
+    }
+    pub fn pivot(&self) -> Option<StaticPoint2d> {
+        self.pivot
+    }
+
+    /// Switches the tool, dropping the selection
+    pub fn select_tool(&mut self, tool: EditTool) {
+        *self = Self {
+            snap_modes: self.snap_modes,
+            tool,
+            pivot: self.pivot,
+            ..Self::default()
+        };
+    }
+
+    /// Rotates and scales around the centroid of the shape again
+    pub fn reset_pivot(&mut self) {
+        self.pivot = None;
+    }
+
+    /// Center of rotation and scaling of `shape`
+    pub fn center(&self, scene: &Scene, shape: Shape) -> StaticPoint2d {
+        self.pivot.unwrap_or_else(|| centroid(scene.shape(shape)))
+    }
+
+    /// Updates the selection and returns the command to apply when a corner is dragged
+    pub fn pointer(&mut self, scene: &Scene, input: &PointerInput) -> Option<Command> {
+        let press_started = input.pressed && !self.pressed;
+        self.pressed = input.pressed;
+        if self.tool != EditTool::Corners {
+            return self.transform_pointer(scene, input, press_started);
+        }
+        if input.pressed {
+            if let Some(corner) = self.selected_corner {
+                self.snap = if input.snapping_disabled {
+                    None
+                } else {
+                    corner_snapper(self.snap_modes, input, &scene.cutter).snap(&input.position)
+                };
+                // a grid point on the line through the fixed corners would flatten the
+                // triangle, which the scene refuses, so the corner would stick there
+                if let Some(snap) = self.snap {
+                    let snapped = Command::MoveCorner {
+                        corner,
+                        to: snap.point,
+                    };
+                    if scene.apply(&snapped).is_none() {
+                        self.snap = None;
+                    }
+                }
+                let to = self.snap.map(|s| s.point).unwrap_or(input.position);
+                return Some(Command::MoveCorner { corner, to });
+            }
+        }
+        self.snap = None;
+        let r = input.resolution * input.resolution * PICK_RADIUS * PICK_RADIUS;
+        self.selected_corner = None;
+        for (idx, pt) in scene.triangle.points().enumerate() {
+            if r >= pt.dist_square(&input.position).0 {
+                self.selected_corner = Some(idx);
+            }
+        }
+        None
+    }
+
+    fn transform_pointer(
+        &mut self,
+        scene: &Scene,
+        input: &PointerInput,
+        press_started: bool,
+    ) -> Option<Command> {
+        if !input.pressed {
+            self.drag = None;
+            self.snap = None;
+            self.hovered_shape = pick_shape(scene, &input.position);
+            return None;
+        }
+        if press_started {
+            match self.hovered_shape {
+                Some(shape) => {
+                    self.drag = Some(TransformDrag {
+                        shape,
+                        original: *scene.shape(shape),
+                        start: input.position,
+                    })
+                }
+                None if self.tool == EditTool::Rotate || self.tool == EditTool::Scale => {
+                    self.snap = if input.snapping_disabled {
+                        None
+                    } else {
+                        scene_snapper(self.snap_modes, input, scene).snap(&input.position)
+                    };
+                    self.pivot = Some(self.snap.map(|s| s.point).unwrap_or(input.position));
+                }
+                None => {}
+            }
+        }
+        let drag = self.drag?;
+        let transform = self.drag_transform(&drag, input);
+        Some(Command::SetShape {
+            shape: drag.shape,
+            to: transform.apply_triangle(&drag.original),
+        })
+    }
+
+    /// Transformation of the dragged shape from where the drag started to the pointer
+    fn drag_transform(&self, drag: &TransformDrag, input: &PointerInput) -> Affine {
+        let center = self.pivot.unwrap_or_else(|| centroid(&drag.original));
+        let snapping = !input.snapping_disabled;
+        let StaticPoint2d { x: cx, y: cy } = center;
+        let StaticPoint2d { x: sx, y: sy } = drag.start;
+        let StaticPoint2d { x, y } = input.position;
+        let (from_x, from_y, to_x, to_y) = (sx.0 - cx.0, sy.0 - cy.0, x.0 - cx.0, y.0 - cy.0);
+        match self.tool {
+            EditTool::Corners | EditTool::Translate => {
+                let (mut dx, mut dy) = (x.0 - sx.0, y.0 - sy.0);
+                if snapping {
+                    let (step_x, step_y) = input.grid_step;
+                    dx = (dx / step_x).round() * step_x;
+                    dy = (dy / step_y).round() * step_y;
+                }
+                Affine::translation(dx, dy)
+            }
+            EditTool::Rotate => {
+                let mut degrees = (to_y.atan2(to_x) - from_y.atan2(from_x)).to_degrees();
+                if snapping {
+                    degrees = (degrees / ROTATION_SNAP_DEGREES).round() * ROTATION_SNAP_DEGREES;
+                }
+                Affine::rotation(&center, degrees)
+            }
+            EditTool::Scale => {
+                let from = from_x.hypot(from_y);
+                let mut factor = if from > 0.0 {
+                    to_x.hypot(to_y) / from
+                } else {
+                    1.0
+                };
+                if snapping {
+                    factor = ((factor / SCALE_SNAP).round() * SCALE_SNAP).max(SCALE_SNAP);
+                }
+                Affine::scaling(&center, factor)
+            }
+        }
+    }
+
+    /// Selection and snap markers to draw on top of the scene
+    pub fn overlay(&self, scene: &Scene) -> Vec<Figure> {
+        let mut figures = Vec::new();
+        if self.tool != EditTool::Corners {
+            figures.extend(self.transform_overlay(scene));
+        }
+        if let Some(marker_pos) = self
+            .selected_corner
+            .and_then(|idx| scene.triangle.get_point(idx))
+        {
+            figures.push(Figure::marker(
+                CssStyle::Color(CssColor::Green),
+                *marker_pos,
+            ));
+        }
+        if let Some(snap) = self.snap {
+            figures.push(Figure::snap_marker(
+                CssStyle::Color(CssColor::Red),
+                snap.point,
+            ));
+        }
+        figures
+    }
+
+    /// Highlight of the hovered or dragged shape and the pivot it turns around
+    fn transform_overlay(&self, scene: &Scene) -> Vec<Figure> {
+        let mut figures = Vec::new();
+        let shape = self.drag.map(|drag| drag.shape).or(self.hovered_shape);
+        if let Some(shape) = shape {
+            figures.push(Figure::polygon(
+                CssStyle::Color(CssColor::Green),
+                scene.shape(shape).to_any_polygon(),
+            ));
+        }
+        if self.tool == EditTool::Rotate || self.tool == EditTool::Scale {
+            let pivot = match (self.pivot, shape) {
+                (Some(pivot), _) => Some(pivot),
+                (None, Some(shape)) => Some(centroid(scene.shape(shape))),
+                (None, None) => None,
+            };
+            if let Some(pivot) = pivot {
+                figures.push(Figure::snap_marker(CssStyle::Color(CssColor::Blue), pivot));
+            }
+        }
+        figures
+    }
+}
+
+/// The shape under the pointer, the cut triangle if it overlaps the cutter there
+fn pick_shape(scene: &Scene, position: &StaticPoint2d) -> Option<Shape> {
+    [Shape::Triangle, Shape::Cutter]
+        .into_iter()
+        .find(|shape| classify(scene.shape(*shape), position) != Containment::Outside)
+}
+
+/// Snap targets for placing the pivot: the corners and edges of both shapes
+fn scene_snapper(modes: SnapModes, input: &PointerInput, scene: &Scene) -> Snapper {
+    Snapper::new(modes, input.resolution * PICK_RADIUS, input.grid_step)
+        .with_vertices(scene.triangle.points())
+        .with_vertices(scene.cutter.points())
+        .with_edges(triangle_edges(&scene.triangle))
+        .with_edges(triangle_edges(&scene.cutter))
+}
+
+/// Index of the point nearest to the pointer within the pick radius
+pub(crate) fn pick_point<'a>(
+    points: impl IntoIterator<Item = &'a StaticPoint2d>,
+    input: &PointerInput,
+) -> Option<usize> {
+    let r = input.resolution * input.resolution * PICK_RADIUS * PICK_RADIUS;
+    points
+        .into_iter()
+        .map(|pt| pt.dist_square(&input.position))
+        .enumerate()
+        .filter(|(_, d)| r >= d.0)
+        .min_by(|(_, d1), (_, d2)| d1.cmp(d2))
+        .map(|(idx, _)| idx)
+}
+
+/// Snap targets for dragging one corner of the triangle: the corners and edges of the
+/// cutter, those of the triangle itself would only flatten it
+fn corner_snapper(
+    modes: SnapModes,
+    input: &PointerInput,
+    cutter: &StaticTriangle2d<StaticPoint2d>,
+) -> Snapper {
+    Snapper::new(modes, input.resolution * PICK_RADIUS, input.grid_step)
+        .with_vertices(cutter.points())
+        .with_edges(triangle_edges(cutter))
+}
+
+fn triangle_edges(
+    triangle: &StaticTriangle2d<StaticPoint2d>,
+) -> [(&StaticPoint2d, &StaticPoint2d); 3] {
+    [
+        (triangle.p1(), triangle.p2()),
+        (triangle.p2(), triangle.p3()),
+        (triangle.p3(), triangle.p1()),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{Point2d, StaticPoint2d, Triangle2d};
+
+    use crate::geometry::snap::SnapKind;
+    use crate::geometry::transform::centroid;
+    use crate::scene::editor::{EditTool, Editor, PointerInput};
+    use crate::scene::Scene;
+
+    fn input(x: f64, y: f64, pressed: bool) -> PointerInput {
+        PointerInput {
+            position: (x, y).into(),
+            pressed,
+            resolution: 0.5,
+            grid_step: (10.0, 10.0),
+            snapping_disabled: false,
+        }
+    }
+
+    #[test]
+    fn test_drag_corner() {
+        let mut scene = Scene::default();
+        let mut editor = Editor::default();
+        assert_eq!(editor.pointer(&scene, &input(1.0, 98.0, false)), None);
+        assert_eq!(editor.selected_corner(), Some(2));
+
+        let command = editor.pointer(&scene, &input(20.7, 61.0, true)).unwrap();
+        scene = scene.apply(&command).unwrap();
+        assert_eq!(editor.snap().map(|s| s.kind), Some(SnapKind::Grid));
+        assert_eq!(
+            scene.triangle.p3().coordinates(),
+            StaticPoint2d::from((20.0, 60.0))
+        );
+
+        editor.pointer(&scene, &input(20.0, 60.0, false));
+        assert_eq!(editor.snap(), None);
+        assert_eq!(editor.selected_corner(), Some(2));
+
+        // the grid point on the base would flatten the triangle, the corner follows the pointer
+        let command = editor.pointer(&scene, &input(50.7, 0.3, true)).unwrap();
+        assert_eq!(editor.snap(), None);
+        scene = scene.apply(&command).unwrap();
+        assert_eq!(
+            scene.triangle.p3().coordinates(),
+            StaticPoint2d::from((50.7, 0.3))
+        );
+    }
+
+    #[test]
+    fn test_transform_tools() {
+        let scene = Scene::default();
+        let inside = centroid(&scene.triangle);
+        let (x, y) = (inside.x.0, inside.y.0);
+        let mut editor = Editor::default();
+        editor.select_tool(EditTool::Translate);
+        editor.pointer(&scene, &input(x, y, false));
+        let command = editor.pointer(&scene, &input(x, y, true)).unwrap();
+        assert_eq!(scene.apply(&command).unwrap().triangle, scene.triangle);
+        // the offset snaps to the grid
+        let command = editor
+            .pointer(&scene, &input(x + 21.0, y - 9.0, true))
+            .unwrap();
+        let moved = scene.apply(&command).unwrap();
+        assert_eq!(
+            moved.triangle.p1().coordinates(),
+            StaticPoint2d::from((
+                scene.triangle.p1().x.0 + 20.0,
+                scene.triangle.p1().y.0 - 10.0
+            ))
+        );
+        assert_eq!(moved.cutter, scene.cutter);
+
+        // a click on empty space places the pivot, dragging rotates in steps of 15°
+        editor.pointer(&scene, &input(x, y, false));
+        editor.select_tool(EditTool::Rotate);
+        editor.pointer(&scene, &input(1000.0, 1000.0, false));
+        editor.pointer(&scene, &input(1000.0, 1000.0, true));
+        assert_eq!(editor.pivot(), Some((1000.0, 1000.0).into()));
+        editor.pointer(&scene, &input(x, y, false));
+        editor.pointer(&scene, &input(x, y, true));
+        // far from the pivot, 100 units sideways turn by less than half a step
+        let command = editor.pointer(&scene, &input(x + 100.0, y, true)).unwrap();
+        assert_eq!(
+            scene.apply(&command).map(|s| s.triangle),
+            Some(scene.triangle)
+        );
+    }
+}
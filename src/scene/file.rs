@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::geometry::coordinates::Coordinates;
+use crate::scene::Scene;
+
+/// Serialized form of a [`Scene`]
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub struct SceneFile {
+    pub triangle: [(f64, f64); 3],
+    pub cutter: [(f64, f64); 3],
+}
+
+impl SceneFile {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("scene is always serializable")
+    }
+}
+
+impl From<&Scene> for SceneFile {
+    fn from(scene: &Scene) -> Self {
+        Self {
+            triangle: corners(&scene.triangle),
+            cutter: corners(&scene.cutter),
+        }
+    }
+}
+
+impl From<&SceneFile> for Scene {
+    fn from(file: &SceneFile) -> Self {
+        Scene {
+            triangle: to_triangle(&file.triangle),
+            cutter: to_triangle(&file.cutter),
+            preset: None,
+        }
+    }
+}
+
+/// Scenes within other serialized values, the preset is not kept
+impl Coordinates for Scene {
+    type Plain = SceneFile;
+
+    fn to_plain(&self) -> Self::Plain {
+        SceneFile::from(self)
+    }
+    fn from_plain(plain: Self::Plain) -> Self {
+        Scene::from(&plain)
+    }
+}
+
+/// Serialized form of a triangle mesh, e.g. `{"triangles": [[[0, 0], [1, 0], [0, 1]]]}`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct MeshFile {
+    pub triangles: Vec<[(f64, f64); 3]>,
+}
+
+impl MeshFile {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("mesh is always serializable")
+    }
+    pub fn to_triangles(&self) -> Vec<StaticTriangle2d<StaticPoint2d>> {
+        self.triangles.iter().map(to_triangle).collect()
+    }
+}
+
+impl From<&[StaticTriangle2d<StaticPoint2d>]> for MeshFile {
+    fn from(triangles: &[StaticTriangle2d<StaticPoint2d>]) -> Self {
+        Self {
+            triangles: triangles.iter().map(corners).collect(),
+        }
+    }
+}
+
+fn corners(triangle: &StaticTriangle2d<StaticPoint2d>) -> [(f64, f64); 3] {
+    [triangle.p1(), triangle.p2(), triangle.p3()].map(|p| {
+        let StaticPoint2d { x, y } = p.coordinates();
+        (x.0, y.0)
+    })
+}
+
+fn to_triangle([p1, p2, p3]: &[(f64, f64); 3]) -> StaticTriangle2d<StaticPoint2d> {
+    StaticTriangle2d::new((*p1).into(), (*p2).into(), (*p3).into())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::cut::CutResult;
+    use crate::scene::file::{MeshFile, SceneFile};
+    use crate::scene::mesh::grid_mesh;
+    use crate::scene::Scene;
+
+    #[test]
+    fn test_round_trip() {
+        let scene = Scene::default();
+        let json = SceneFile::from(&scene).to_json();
+        let loaded = Scene::from(&SceneFile::from_json(&json).unwrap());
+        assert_eq!(loaded.triangle, scene.triangle);
+        assert_eq!(loaded.cutter, scene.cutter);
+
+        let result = scene.cut();
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(serde_json::from_str::<CutResult>(&json).unwrap(), result);
+
+        let mesh = grid_mesh(2, 2, 1.5);
+        let json = MeshFile::from(mesh.as_slice()).to_json();
+        assert_eq!(MeshFile::from_json(&json).unwrap().to_triangles(), mesh);
+        let pasted = MeshFile::from_json(r#"{"triangles": [[[0, 0], [1, 0], [0, 1]]]}"#);
+        assert_eq!(pasted.unwrap().triangles.len(), 1);
+    }
+}
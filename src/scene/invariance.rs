@@ -0,0 +1,469 @@
+//! Invariance of the cut under rigid motions and reordered corners.
+//!
+//! Cutting a translated, rotated or mirrored copy of a scene and mapping the pieces back
+//! has to give the pieces of the scene itself, up to rounding. The same holds for
+//! listing the corners of either triangle in another order. Bugs which only show up in
+//! some orientations break this.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Point2d, Polygon2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::cut::CutResult;
+use crate::geometry::exact::RELATIVE_TOLERANCE;
+use crate::geometry::guard::{guarded_cut, CutFailure};
+use crate::geometry::transform::{centroid, Affine};
+use crate::scene::{triangle_figures, Scene};
+
+type Triangle = StaticTriangle2d<StaticPoint2d>;
+
+/// Offsets of the translated copies, in multiples of the scene extent
+const TRANSLATIONS: [(f64, f64); 4] = [(10.0, 0.0), (0.0, -10.0), (-10.0, 10.0), (1e3, 1e3)];
+
+/// Angle between the rotated copies
+const ROTATION_STEP_DEGREES: f64 = 7.5;
+
+/// All orders of three corners, the first one keeps them as they are
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [0, 2, 1],
+    [2, 1, 0],
+    [1, 0, 2],
+];
+
+/// Change of the scene the cut should not depend on
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Debug)]
+pub enum Variation {
+    Translation {
+        dx: f64,
+        dy: f64,
+    },
+    /// Counter-clockwise by degrees around the centroid of the cut triangle
+    Rotation(f64),
+    /// At the vertical line through the centroid of the cut triangle
+    Mirror,
+    /// Corners of the cut triangle and the cutter in the given order
+    Permutation {
+        triangle: [usize; 3],
+        cutter: [usize; 3],
+    },
+}
+
+impl Variation {
+    /// Every variation checked for `scene`
+    pub fn all(scene: &Scene) -> Vec<Variation> {
+        let extent = extent(scene);
+        let mut variations: Vec<_> = TRANSLATIONS
+            .iter()
+            .map(|(dx, dy)| Variation::Translation {
+                dx: dx * extent,
+                dy: dy * extent,
+            })
+            .collect();
+        let rotations = (360.0 / ROTATION_STEP_DEGREES) as usize;
+        variations.extend(
+            (1..rotations).map(|step| Variation::Rotation(step as f64 * ROTATION_STEP_DEGREES)),
+        );
+        variations.push(Variation::Mirror);
+        for triangle in PERMUTATIONS {
+            for cutter in PERMUTATIONS {
+                if (triangle, cutter) != (PERMUTATIONS[0], PERMUTATIONS[0]) {
+                    variations.push(Variation::Permutation { triangle, cutter });
+                }
+            }
+        }
+        variations
+    }
+
+    /// Moves the scene, reordering corners moves nothing
+    fn transform(&self, scene: &Scene) -> Affine {
+        let center = centroid(&scene.triangle);
+        match self {
+            Variation::Translation { dx, dy } => Affine::translation(*dx, *dy),
+            Variation::Rotation(degrees) => Affine::rotation(&center, *degrees),
+            Variation::Mirror => Affine::mirror_x(&center),
+            Variation::Permutation { .. } => Affine::IDENTITY,
+        }
+    }
+
+    /// Cut triangle and cutter of the varied scene
+    fn apply(&self, scene: &Scene, transform: &Affine) -> (Triangle, Triangle) {
+        let (triangle, cutter) = match self {
+            Variation::Permutation { triangle, cutter } => (
+                permuted(&scene.triangle, triangle),
+                permuted(&scene.cutter, cutter),
+            ),
+            _ => (scene.triangle, scene.cutter),
+        };
+        (
+            transform.apply_triangle(&triangle),
+            transform.apply_triangle(&cutter),
+        )
+    }
+}
+
+impl fmt::Display for Variation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variation::Translation { dx, dy } => write!(f, "translated by ({dx:.3}, {dy:.3})"),
+            Variation::Rotation(degrees) => write!(f, "rotated by {degrees:.1}°"),
+            Variation::Mirror => write!(f, "mirrored"),
+            Variation::Permutation { triangle, cutter } => {
+                let order = |prefix: &str, order: &[usize; 3]| {
+                    order
+                        .iter()
+                        .map(|idx| format!("{prefix}{}", idx + 1))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                write!(
+                    f,
+                    "corners {}, {}",
+                    order("p", triangle),
+                    order("c", cutter)
+                )
+            }
+        }
+    }
+}
+
+/// How the mapped back cut of a variation differs from the cut of the scene
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum Difference {
+    /// The cut of the variation failed with the message, or `None` if it succeeded
+    /// where the cut of the scene failed
+    Failure(Option<String>),
+    /// Inside and outside areas of the variation minus the ones of the scene
+    Area { inside: f64, outside: f64 },
+    /// Number of inside and outside pieces
+    PieceCount {
+        scene: [usize; 2],
+        variation: [usize; 2],
+    },
+    /// Corners of the pieces of one cut with no corner of the other cut nearby
+    Corners(#[serde(with = "crate::geometry::coordinates")] Vec<StaticPoint2d>),
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Failure(Some(message)) => write!(f, "cut failed: {message}"),
+            Difference::Failure(None) => write!(f, "cut succeeded"),
+            Difference::Area { inside, outside } => write!(
+                f,
+                "areas differ by {inside:.1e} inside, {outside:.1e} outside"
+            ),
+            Difference::PieceCount { scene, variation } => write!(
+                f,
+                "{} inside and {} outside pieces instead of {} and {}",
+                variation[0], variation[1], scene[0], scene[1]
+            ),
+            Difference::Corners(corners) => write!(f, "{} corners moved", corners.len()),
+        }
+    }
+}
+
+/// A variation whose cut differs from the one of the scene
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Deviation {
+    pub variation: Variation,
+    pub differences: Vec<Difference>,
+    /// Pieces of the variation mapped back onto the scene, `None` if its cut failed
+    pub result: Option<CutResult>,
+}
+
+impl Deviation {
+    /// The mapped back pieces and the corners without counterpart
+    pub fn figures(&self) -> Vec<Figure> {
+        let mut figures = Vec::new();
+        if let Some(result) = &self.result {
+            figures.extend(triangle_figures(&result.inside, CssColor::Green));
+            figures.extend(triangle_figures(&result.outside, CssColor::Red));
+        }
+        for difference in &self.differences {
+            if let Difference::Corners(corners) = difference {
+                figures.extend(
+                    corners
+                        .iter()
+                        .map(|pt| Figure::snap_marker(CssStyle::Color(CssColor::Red), *pt)),
+                );
+            }
+        }
+        figures
+    }
+}
+
+impl fmt::Display for Deviation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.variation)?;
+        for (idx, difference) in self.differences.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{difference}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Variations of a scene whose cut differs from the cut of the scene
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct InvarianceCheck {
+    /// Scene the variations were derived from
+    #[serde(with = "crate::geometry::coordinates")]
+    pub scene: Scene,
+    /// Number of variations cut
+    pub checked: usize,
+    pub deviations: Vec<Deviation>,
+}
+
+impl InvarianceCheck {
+    /// Cuts every [`Variation::all`] of `scene` and compares it with the cut of `scene`
+    pub fn run(scene: &Scene) -> Self {
+        let original = scene.guarded_cut();
+        let variations = Variation::all(scene);
+        let deviations = variations
+            .iter()
+            .filter_map(|variation| {
+                let transform = variation.transform(scene);
+                let back = transform.inverse().expect("rigid motions can be undone");
+                let (triangle, cutter) = variation.apply(scene, &transform);
+                let result = guarded_cut(&triangle, &cutter).map(|result| mapped(&result, &back));
+                let tolerance = scale(&[triangle, cutter]) * RELATIVE_TOLERANCE;
+                let differences = compare(scene, &original, &result, tolerance);
+                (!differences.is_empty()).then(|| Deviation {
+                    variation: *variation,
+                    differences,
+                    result: result.ok(),
+                })
+            })
+            .collect();
+        Self {
+            scene: *scene,
+            checked: variations.len(),
+            deviations,
+        }
+    }
+
+    /// Whether the check was run on the triangles of `scene`
+    pub fn applies_to(&self, scene: &Scene) -> bool {
+        self.scene.triangle == scene.triangle && self.scene.cutter == scene.cutter
+    }
+}
+
+/// Differences of the mapped back `variation` result from the `original` cut of
+/// `scene`, corners closer than `tolerance` count as the same
+fn compare(
+    scene: &Scene,
+    original: &Result<CutResult, CutFailure>,
+    variation: &Result<CutResult, CutFailure>,
+    tolerance: f64,
+) -> Vec<Difference> {
+    let (original, variation) = match (original, variation) {
+        (Ok(original), Ok(variation)) => (original, variation),
+        (Err(_), Err(_)) => return Vec::new(),
+        (Ok(_), Err(failure)) => return vec![Difference::Failure(Some(failure.message.clone()))],
+        (Err(_), Ok(_)) => return vec![Difference::Failure(None)],
+    };
+    let mut differences = Vec::new();
+    // moving every corner by the tolerance changes the areas by about the tolerance
+    // times the extent of the scene
+    let area_tolerance =
+        (scene.triangle.area().0.abs() * RELATIVE_TOLERANCE).max(tolerance * extent(scene));
+    let inside = variation.inside_area() - original.inside_area();
+    let outside = variation.outside_area() - original.outside_area();
+    if inside.abs() > area_tolerance || outside.abs() > area_tolerance {
+        differences.push(Difference::Area { inside, outside });
+    }
+    let counts = |result: &CutResult| [result.inside.len(), result.outside.len()];
+    if counts(original) != counts(variation) {
+        differences.push(Difference::PieceCount {
+            scene: counts(original),
+            variation: counts(variation),
+        });
+    }
+    let (original_corners, variation_corners) = (corners(original), corners(variation));
+    let mut unmatched: Vec<StaticPoint2d> = Vec::new();
+    for (from, to) in [
+        (&original_corners, &variation_corners),
+        (&variation_corners, &original_corners),
+    ] {
+        for pt in from {
+            let matched = to.iter().any(|other| distance(pt, other) <= tolerance);
+            if !matched && !unmatched.contains(pt) {
+                unmatched.push(*pt);
+            }
+        }
+    }
+    if !unmatched.is_empty() {
+        differences.push(Difference::Corners(unmatched));
+    }
+    differences
+}
+
+/// Pieces of `result` moved by `transform`
+fn mapped(result: &CutResult, transform: &Affine) -> CutResult {
+    CutResult {
+        inside: result
+            .inside
+            .iter()
+            .map(|t| transform.apply_triangle(t))
+            .collect(),
+        outside: result
+            .outside
+            .iter()
+            .map(|t| transform.apply_triangle(t))
+            .collect(),
+    }
+}
+
+fn permuted(triangle: &Triangle, order: &[usize; 3]) -> Triangle {
+    let corners = [*triangle.p1(), *triangle.p2(), *triangle.p3()];
+    StaticTriangle2d::new(corners[order[0]], corners[order[1]], corners[order[2]])
+}
+
+fn corners(result: &CutResult) -> Vec<StaticPoint2d> {
+    result
+        .inside
+        .iter()
+        .chain(result.outside.iter())
+        .flat_map(|t| [*t.p1(), *t.p2(), *t.p3()])
+        .collect()
+}
+
+fn distance(a: &StaticPoint2d, b: &StaticPoint2d) -> f64 {
+    (a.x.0 - b.x.0).hypot(a.y.0 - b.y.0)
+}
+
+/// Largest absolute coordinate, the rounding errors of the cut grow with it
+fn scale(triangles: &[Triangle]) -> f64 {
+    triangles
+        .iter()
+        .flat_map(|t| [*t.p1(), *t.p2(), *t.p3()])
+        .map(|p| {
+            let StaticPoint2d { x, y } = p.coordinates();
+            x.0.abs().max(y.0.abs())
+        })
+        .fold(f64::MIN_POSITIVE, f64::max)
+}
+
+/// Width or height of the box around both triangles, whichever is larger
+fn extent(scene: &Scene) -> f64 {
+    let points: Vec<_> = scene
+        .triangle
+        .points()
+        .chain(scene.cutter.points())
+        .collect();
+    let span = |coordinate: fn(&StaticPoint2d) -> f64| {
+        let values = points.iter().map(|p| coordinate(p));
+        let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+        max - min
+    };
+    span(|p| p.x.0).max(span(|p| p.y.0))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::cut::CutResult;
+    use crate::geometry::exact::RELATIVE_TOLERANCE;
+    use crate::geometry::guard::{guarded_cut, CutFailure};
+    use crate::scene::invariance::{
+        compare, mapped, scale, Difference, InvarianceCheck, Variation,
+    };
+    use crate::scene::{Command, Scene};
+
+    #[test]
+    fn test_variations() {
+        let scene = Scene::default();
+        let variations = Variation::all(&scene);
+        // translations, rotations, the mirror image and the reordered corners
+        assert_eq!(variations.len(), 4 + 47 + 1 + 35);
+        assert_eq!(
+            variations.last().unwrap().to_string(),
+            "corners p2 p1 p3, c2 c1 c3"
+        );
+        assert_eq!(Variation::Rotation(7.5).to_string(), "rotated by 7.5°");
+    }
+
+    #[test]
+    fn test_compare() {
+        let scene = Scene::default();
+        let original = scene.cut();
+        assert_eq!(
+            compare(&scene, &Ok(original.clone()), &Ok(original.clone()), 1e-9),
+            Vec::new()
+        );
+
+        let swapped = CutResult {
+            inside: original.outside.clone(),
+            outside: original.inside.clone(),
+        };
+        let differences = compare(&scene, &Ok(original.clone()), &Ok(swapped), 1e-9);
+        assert!(matches!(differences[0], Difference::Area { .. }));
+
+        let failure = CutFailure {
+            message: "boom".to_string(),
+            triangle: scene.triangle,
+            cutter: scene.cutter,
+        };
+        assert_eq!(
+            compare(&scene, &Ok(original), &Err(failure.clone()), 1e-9),
+            vec![Difference::Failure(Some("boom".to_string()))]
+        );
+        assert_eq!(
+            compare(&scene, &Err(failure.clone()), &Err(failure), 1e-9),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let scene = Scene::default();
+        let check = InvarianceCheck::run(&scene);
+        assert_eq!(check.checked, Variation::all(&scene).len());
+        // the default scene is cut the same way in every variation
+        assert_eq!(check.deviations, Vec::new());
+        assert!(check.applies_to(&scene));
+        let moved = scene
+            .apply(&Command::MoveCorner {
+                corner: 2,
+                to: (0.0, 60.0).into(),
+            })
+            .unwrap();
+        assert!(!check.applies_to(&moved));
+    }
+
+    #[test]
+    fn test_wrong_transform() {
+        let scene = Scene::default();
+        let original = scene.guarded_cut();
+        let variation = Variation::Rotation(90.0);
+        let transform = variation.transform(&scene);
+        let (triangle, cutter) = variation.apply(&scene, &transform);
+        let rotated = guarded_cut(&triangle, &cutter).unwrap();
+        let tolerance = scale(&[triangle, cutter]) * RELATIVE_TOLERANCE;
+
+        let back = transform.inverse().unwrap();
+        let mapped_back = Ok(mapped(&rotated, &back));
+        assert_eq!(
+            compare(&scene, &original, &mapped_back, tolerance),
+            Vec::new()
+        );
+
+        // rotating once more leaves the pieces turned by 180° with the same areas
+        let turned = Ok(mapped(&rotated, &transform));
+        let differences = compare(&scene, &original, &turned, tolerance);
+        assert!(
+            differences
+                .iter()
+                .any(|difference| matches!(difference, Difference::Corners(_))),
+            "{differences:?}"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Measure mode of the canvas: distances, angles and the metrics of a clicked triangle
+
+use std::fmt;
+
+use triangles::prelude::{Polygon2d, StaticPoint2d, StaticTriangle2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::containment::{classify, Containment};
+use crate::geometry::measure::{angle_at, Distance, TriangleMetrics};
+use crate::geometry::snap::{SnapModes, SnapTarget, Snapper};
+use crate::geometry::transform::centroid;
+use crate::scene::editor::{PointerInput, PICK_RADIUS};
+
+/// What clicks measure
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum MeasureTool {
+    /// Two clicked points
+    #[default]
+    Distance,
+    /// Three clicked points, the angle is at the second one
+    Angle,
+    /// The triangle under the click
+    Figure,
+}
+
+impl MeasureTool {
+    /// Number of points to click for a measurement
+    fn points(&self) -> usize {
+        match self {
+            MeasureTool::Distance => 2,
+            MeasureTool::Angle => 3,
+            MeasureTool::Figure => 1,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Measurement {
+    Distance(Distance),
+    /// Degrees, `None` if two of the points coincide
+    Angle(Option<f64>),
+    Figure(TriangleMetrics),
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Measurement::Distance(d) => write!(
+                f,
+                "distance: {:.3}, dx: {:.3}, dy: {:.3}",
+                d.distance, d.dx, d.dy
+            ),
+            Measurement::Angle(Some(degrees)) => write!(f, "angle: {degrees:.2}°"),
+            Measurement::Angle(None) => write!(f, "angle: undefined, two points coincide"),
+            Measurement::Figure(metrics) => write!(
+                f,
+                "area: {:.3}, perimeter: {:.3}, signed area: {:.3} ({})",
+                metrics.area(),
+                metrics.perimeter,
+                metrics.signed_area,
+                metrics.orientation
+            ),
+        }
+    }
+}
+
+/// Interaction state of the measure mode
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Measurer {
+    pub tool: MeasureTool,
+    /// Clicked points, a click after the last one of a measurement starts the next one
+    points: Vec<StaticPoint2d>,
+    figure: Option<StaticTriangle2d<StaticPoint2d>>,
+    /// Where a click would place the next point
+    hover: Option<StaticPoint2d>,
+    snap: Option<SnapTarget>,
+    pressed: bool,
+}
+
+impl Measurer {
+    /// Tracks the pointer over `triangles`, whose corners the clicked points snap to and
+    /// which can be clicked with [`MeasureTool::Figure`]
+    pub fn pointer(&mut self, triangles: &[StaticTriangle2d<StaticPoint2d>], input: &PointerInput) {
+        let press_started = input.pressed && !self.pressed;
+        self.pressed = input.pressed;
+        self.snap = if input.snapping_disabled {
+            None
+        } else {
+            vertex_snapper(triangles, input).snap(&input.position)
+        };
+        let position = self.snap.map(|s| s.point).unwrap_or(input.position);
+        self.hover = Some(position);
+        if !press_started {
+            return;
+        }
+        match self.tool {
+            // a corner of the picked triangle is not inside it any more than elsewhere
+            MeasureTool::Figure => self.figure = pick_triangle(triangles, &input.position),
+            MeasureTool::Distance | MeasureTool::Angle => {
+                if self.points.len() >= self.tool.points() {
+                    self.points.clear();
+                }
+                self.points.push(position);
+            }
+        }
+    }
+
+    /// Switches the tool, dropping the current measurement
+    pub fn select_tool(&mut self, tool: MeasureTool) {
+        *self = Self {
+            tool,
+            ..Self::default()
+        };
+    }
+
+    /// The finished measurement, or the one in progress up to the hovered point
+    pub fn measurement(&self) -> Option<Measurement> {
+        match self.tool {
+            MeasureTool::Figure => self
+                .figure
+                .as_ref()
+                .map(|figure| Measurement::Figure(TriangleMetrics::of(figure))),
+            MeasureTool::Distance => match self.measured_points()[..] {
+                [from, to] => Some(Measurement::Distance(Distance::between(&from, &to))),
+                _ => None,
+            },
+            MeasureTool::Angle => match self.measured_points()[..] {
+                [from, vertex, to] => Some(Measurement::Angle(angle_at(&from, &vertex, &to))),
+                _ => None,
+            },
+        }
+    }
+
+    /// Annotations of the measurement, along with the snap marker
+    pub fn figures(&self) -> Vec<Figure> {
+        let style = CssStyle::Color(CssColor::Red);
+        let points = self.measured_points();
+        let mut figures: Vec<_> = self
+            .points
+            .iter()
+            .map(|pt| Figure::marker(style.clone(), *pt))
+            .collect();
+        match (self.tool, &points[..]) {
+            (MeasureTool::Distance, [from, to]) => {
+                figures.push(Figure::dimension(style.clone(), *from, *to));
+                // legs of the delta x and delta y
+                let corner: StaticPoint2d = (to.x.0, from.y.0).into();
+                figures.push(Figure::lines(style.clone(), vec![*from, corner, *to]));
+            }
+            (MeasureTool::Angle, [first, rest @ ..]) if !rest.is_empty() => {
+                figures.push(Figure::lines(style.clone(), points.clone()));
+                if let [vertex, to] = rest {
+                    figures.extend(angle_arc(style.clone(), first, vertex, to));
+                }
+            }
+            _ => {}
+        }
+        if let Some(figure) = self.figure.filter(|_| self.tool == MeasureTool::Figure) {
+            figures.push(Figure::polygon(style.clone(), figure.to_any_polygon()));
+            figures.push(Figure::label(
+                style.clone(),
+                centroid(&figure),
+                format!("{:.3}", TriangleMetrics::of(&figure).area()),
+            ));
+        }
+        if let Some(snap) = self.snap {
+            figures.push(Figure::snap_marker(style, snap.point));
+        }
+        figures
+    }
+
+    /// Clicked points followed by the hovered one while the measurement is incomplete
+    fn measured_points(&self) -> Vec<StaticPoint2d> {
+        let mut points = self.points.clone();
+        if points.len() < self.tool.points() {
+            points.extend(self.hover);
+        }
+        points
+    }
+}
+
+/// Arc and label of the angle at `vertex`, sized by the shorter leg
+fn angle_arc(
+    style: CssStyle,
+    from: &StaticPoint2d,
+    vertex: &StaticPoint2d,
+    to: &StaticPoint2d,
+) -> Vec<Figure> {
+    let Some(degrees) = angle_at(from, vertex, to) else {
+        return Vec::new();
+    };
+    let direction = |pt: &StaticPoint2d| (pt.y.0 - vertex.y.0).atan2(pt.x.0 - vertex.x.0);
+    let radius = Distance::between(vertex, from)
+        .distance
+        .min(Distance::between(vertex, to).distance)
+        / 4.0;
+    let (start, end) = (direction(from), direction(to));
+    // the arc runs counter-clockwise, start at the leg from which the angle is below 180°
+    let (start, end) = if (end - start).rem_euclid(std::f64::consts::TAU) <= std::f64::consts::PI {
+        (start, end)
+    } else {
+        (end, start)
+    };
+    let middle = start + (end - start).rem_euclid(std::f64::consts::TAU) / 2.0;
+    let anchor: StaticPoint2d = (
+        vertex.x.0 + 1.5 * radius * middle.cos(),
+        vertex.y.0 + 1.5 * radius * middle.sin(),
+    )
+        .into();
+    vec![
+        Figure::arc(style.clone(), *vertex, radius, start, end),
+        Figure::label(style, anchor, format!("{degrees:.1}°")),
+    ]
+}
+
+/// Snaps clicked points onto the corners of the measured triangles
+fn vertex_snapper(triangles: &[StaticTriangle2d<StaticPoint2d>], input: &PointerInput) -> Snapper {
+    let modes = SnapModes {
+        grid: false,
+        integer: false,
+        vertices: true,
+        edges: false,
+        midpoints: false,
+        intersections: false,
+    };
+    Snapper::new(modes, input.resolution * PICK_RADIUS, input.grid_step)
+        .with_vertices(triangles.iter().flat_map(|triangle| triangle.points()))
+}
+
+/// The smallest of the triangles containing `position`, result pieces lie within the
+/// input triangles and are the more specific pick
+fn pick_triangle(
+    triangles: &[StaticTriangle2d<StaticPoint2d>],
+    position: &StaticPoint2d,
+) -> Option<StaticTriangle2d<StaticPoint2d>> {
+    triangles
+        .iter()
+        .filter(|triangle| classify(triangle, position) != Containment::Outside)
+        .min_by(|a, b| {
+            let area = |t: &StaticTriangle2d<StaticPoint2d>| TriangleMetrics::of(t).area();
+            area(a).total_cmp(&area(b))
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{StaticPoint2d, StaticTriangle2d};
+
+    use crate::geometry::measure::Orientation;
+    use crate::scene::editor::PointerInput;
+    use crate::scene::measure::{MeasureTool, Measurement, Measurer};
+
+    fn input(x: f64, y: f64, pressed: bool) -> PointerInput {
+        PointerInput {
+            position: (x, y).into(),
+            pressed,
+            resolution: 0.5,
+            grid_step: (10.0, 10.0),
+            snapping_disabled: false,
+        }
+    }
+
+    fn click(
+        measurer: &mut Measurer,
+        triangles: &[StaticTriangle2d<StaticPoint2d>],
+        x: f64,
+        y: f64,
+    ) {
+        measurer.pointer(triangles, &input(x, y, true));
+        measurer.pointer(triangles, &input(x, y, false));
+    }
+
+    fn triangles() -> Vec<StaticTriangle2d<StaticPoint2d>> {
+        vec![
+            StaticTriangle2d::new((0.0, 0.0).into(), (40.0, 0.0).into(), (0.0, 30.0).into()),
+            StaticTriangle2d::new((0.0, 0.0).into(), (10.0, 0.0).into(), (0.0, 10.0).into()),
+        ]
+    }
+
+    #[test]
+    fn test_distance() {
+        let triangles = triangles();
+        let mut measurer = Measurer::default();
+        // snaps onto the corners within the pick radius of 5 world units
+        click(&mut measurer, &triangles, 1.0, 2.0);
+        measurer.pointer(&triangles, &input(20.0, 20.0, false));
+        assert!(matches!(
+            measurer.measurement(),
+            Some(Measurement::Distance(d)) if d.dx == 20.0 && d.dy == 20.0
+        ));
+        click(&mut measurer, &triangles, 38.0, -1.0);
+        let Some(Measurement::Distance(distance)) = measurer.measurement() else {
+            panic!("no distance measured");
+        };
+        assert_eq!(
+            (distance.distance, distance.dx, distance.dy),
+            (40.0, 40.0, 0.0)
+        );
+        // a third click starts over
+        click(&mut measurer, &triangles, 20.0, 20.0);
+        measurer.pointer(&triangles, &input(20.0, 20.0, false));
+        assert!(matches!(
+            measurer.measurement(),
+            Some(Measurement::Distance(d)) if d.distance == 0.0
+        ));
+    }
+
+    #[test]
+    fn test_angle() {
+        let triangles = triangles();
+        let mut measurer = Measurer::default();
+        measurer.select_tool(MeasureTool::Angle);
+        click(&mut measurer, &triangles, 40.0, 1.0);
+        click(&mut measurer, &triangles, 0.0, 0.0);
+        click(&mut measurer, &triangles, 1.0, 30.0);
+        let Some(Measurement::Angle(Some(degrees))) = measurer.measurement() else {
+            panic!("no angle measured");
+        };
+        assert!((degrees - 90.0).abs() < 1e-12);
+        assert_eq!(measurer.figures().len(), 3 + 3 + 1);
+    }
+
+    #[test]
+    fn test_figure() {
+        let triangles = triangles();
+        let mut measurer = Measurer::default();
+        measurer.select_tool(MeasureTool::Figure);
+        click(&mut measurer, &triangles, 2.0, 2.0);
+        let Some(Measurement::Figure(metrics)) = measurer.measurement() else {
+            panic!("no figure measured");
+        };
+        // the smaller triangle is picked
+        assert_eq!(metrics.area(), 50.0);
+        assert_eq!(metrics.orientation, Orientation::CounterClockwise);
+
+        click(&mut measurer, &triangles, 100.0, 100.0);
+        assert_eq!(measurer.measurement(), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Input of the mesh page: a whole triangle mesh cut by a polygon with holes.
+//!
+//! `cut_to_triangles` takes a triangle as cutter, so the cutter polygon is split into
+//! triangles by ear clipping first. Every mesh triangle is cut by the first of them, the
+//! pieces outside of it by the second one, and so on. What is left after the last one
+//! lies outside of the polygon.
+
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Polygon2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::cut::CutResult;
+use crate::geometry::ear_clipping::EarClipping;
+use crate::geometry::guard::{guarded_cut, CutFailure};
+use crate::scene::polygon::PolygonScene;
+use crate::scene::triangle_figures;
+
+type Triangle = StaticTriangle2d<StaticPoint2d>;
+
+/// Regular grid of `columns` by `rows` square cells of width `size` starting at the
+/// origin, each cell split into two counter-clockwise triangles along its diagonal
+pub fn grid_mesh(columns: usize, rows: usize, size: f64) -> Vec<Triangle> {
+    let pt = |column: usize, row: usize| -> StaticPoint2d {
+        (column as f64 * size, row as f64 * size).into()
+    };
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (column, row)))
+        .flat_map(|(column, row)| {
+            let (a, b) = (pt(column, row), pt(column + 1, row));
+            let (c, d) = (pt(column + 1, row + 1), pt(column, row + 1));
+            [
+                StaticTriangle2d::new(a, b, c),
+                StaticTriangle2d::new(a, c, d),
+            ]
+        })
+        .collect()
+}
+
+/// Cutter polygon of the mesh page before it is edited, covering the middle of a
+/// 10 by 10 grid of 10 wide cells, with a hole
+pub fn default_cutter() -> PolygonScene {
+    let ring = |coords: &[(f64, f64)]| coords.iter().copied().map(StaticPoint2d::from).collect();
+    PolygonScene {
+        rings: vec![
+            ring(&[
+                (22.0, 13.0),
+                (75.0, 8.0),
+                (88.0, 55.0),
+                (50.0, 87.0),
+                (13.0, 61.0),
+            ]),
+            ring(&[(40.0, 35.0), (57.0, 41.0), (44.0, 56.0)]),
+        ],
+    }
+}
+
+/// Pieces of every mesh triangle after cutting it with a polygon
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct MeshCut {
+    /// Pieces covered by the polygon and pieces outside of it
+    pub result: CutResult,
+    /// Triangulation of the polygon, one cut per triangle and piece
+    pub cutter: EarClipping,
+    /// Number of calls of `cut_to_triangles`
+    pub cuts: usize,
+    /// Cuts which panicked or were refused, their pieces are counted as outside
+    pub failures: Vec<CutFailure>,
+    /// Milliseconds the cuts of all mesh triangles took
+    pub millis: f64,
+}
+
+impl MeshCut {
+    /// Cuts every triangle of `mesh`, timing the cuts with `now`, which returns
+    /// milliseconds
+    pub fn run(mesh: &[Triangle], cutter: &PolygonScene, mut now: impl FnMut() -> f64) -> Self {
+        let clipping = cutter.triangulate();
+        // collinear ears cover nothing and would only stress the cut with degenerate input
+        let cutter_triangles: Vec<Triangle> = clipping
+            .triangles()
+            .into_iter()
+            .filter(|triangle| triangle.area().0 != 0.0)
+            .collect();
+        let mut cut = MeshCut {
+            cutter: clipping,
+            ..MeshCut::default()
+        };
+        let start = now();
+        for triangle in mesh {
+            let mut outside = vec![*triangle];
+            for cutter_triangle in &cutter_triangles {
+                let mut remaining = Vec::new();
+                for piece in outside {
+                    cut.cuts += 1;
+                    match guarded_cut(&piece, cutter_triangle) {
+                        Ok(result) => {
+                            cut.result.inside.extend(result.inside);
+                            remaining.extend(result.outside);
+                        }
+                        Err(failure) => {
+                            cut.failures.push(failure);
+                            remaining.push(piece);
+                        }
+                    }
+                }
+                outside = remaining;
+            }
+            cut.result.outside.extend(outside);
+        }
+        cut.millis = now() - start;
+        cut
+    }
+
+    /// Outlines of the failed pieces
+    pub fn failure_figures(&self) -> Vec<Figure> {
+        triangle_figures(
+            &self
+                .failures
+                .iter()
+                .map(|failure| failure.triangle)
+                .collect::<Vec<_>>(),
+            CssColor::Black,
+        )
+    }
+}
+
+/// Inside pieces in green and outside pieces in red
+pub fn mesh_figures(result: &CutResult) -> (Vec<Figure>, Vec<Figure>) {
+    (
+        triangle_figures(&result.inside, CssColor::Green),
+        triangle_figures(&result.outside, CssColor::Red),
+    )
+}
+
+/// Outline and corners of the cutter polygon
+pub fn cutter_figures(cutter: &PolygonScene) -> Vec<Figure> {
+    let mut figures = Vec::new();
+    for ring in cutter.rings.iter().filter(|ring| !ring.is_empty()) {
+        let mut outline = ring.clone();
+        outline.push(ring[0]);
+        figures.push(Figure::lines(CssStyle::Color(CssColor::Black), outline));
+        figures.extend(
+            ring.iter()
+                .map(|pt| Figure::marker(CssStyle::Color(CssColor::Black), *pt)),
+        );
+    }
+    figures
+}
+
+/// Sum of the absolute triangle areas
+pub fn mesh_area(mesh: &[Triangle]) -> f64 {
+    mesh.iter().map(|t| t.area().0.abs()).sum()
+}
+
+/// Outlines of the mesh triangles
+pub fn input_figures(mesh: &[Triangle]) -> Vec<Figure> {
+    mesh.iter()
+        .map(|t| Figure::polygon(CssStyle::Color(CssColor::Blue), t.to_any_polygon()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{StaticPoint2d, Triangle2d};
+
+    use crate::scene::mesh::{default_cutter, grid_mesh, mesh_area, MeshCut};
+    use crate::scene::polygon::PolygonScene;
+
+    #[test]
+    fn test_grid_mesh() {
+        let mesh = grid_mesh(3, 2, 10.0);
+        assert_eq!(mesh.len(), 12);
+        assert!(mesh.iter().all(|t| t.area().0 == 50.0));
+        assert_eq!(mesh_area(&mesh), 600.0);
+        assert_eq!(*mesh[11].p3(), StaticPoint2d::from((20.0, 20.0)));
+    }
+
+    #[test]
+    fn test_mesh_cut() {
+        let mesh = grid_mesh(10, 10, 10.0);
+        let cutter = default_cutter();
+        let mut clock = [2.0, 9.5].into_iter();
+        let cut = MeshCut::run(&mesh, &cutter, || clock.next().unwrap());
+        assert_eq!(cut.millis, 7.5);
+        assert!(cut.cutter.complete);
+        // every triangle is cut at least by the first cutter triangle
+        assert!(cut.cuts >= mesh.len());
+        assert_eq!(cut.failures, Vec::new());
+        // the pieces cover the mesh, the inside ones exactly the polygon without its hole
+        let (inside, outside) = (cut.result.inside_area(), cut.result.outside_area());
+        let area = mesh_area(&mesh);
+        let tolerance = 1e-9 * area;
+        assert!(
+            (inside + outside - area).abs() <= tolerance,
+            "{inside} + {outside} != {area}"
+        );
+        assert!(
+            (inside - cutter.area()).abs() <= tolerance,
+            "{inside} != {}",
+            cutter.area()
+        );
+
+        // without a cutter every triangle stays outside, uncut
+        let cut = MeshCut::run(&mesh, &PolygonScene { rings: Vec::new() }, || 0.0);
+        assert_eq!((cut.cuts, cut.result.outside), (0, mesh));
+    }
+}
@@ -0,0 +1,458 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use num_traits::Zero;
+use triangles::prelude::{Number, Point2d, Polygon2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::cut::{cut_triangle, CutResult};
+use crate::geometry::exact::{compare, PrecisionReport};
+use crate::geometry::guard::{guarded_cut, CutFailure};
+use crate::geometry::measure::{Orientation, TriangleMetrics};
+use crate::geometry::presets::Preset;
+use crate::geometry::segments::{segment_intersection, Segment};
+use crate::geometry::transform::{centroid, Affine};
+
+pub mod compute;
+pub mod editor;
+pub mod file;
+pub mod invariance;
+pub mod measure;
+pub mod mesh;
+pub mod points;
+pub mod polygon;
+pub mod reproduction;
+pub mod sweep;
+
+/// Input of the cut page: the triangle which gets cut and the triangle cutting it
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Scene {
+    /// Keeps the winding of its corners while edited, only
+    /// [`Command::FlipOrientation`] changes it
+    pub triangle: StaticTriangle2d<StaticPoint2d>,
+    pub cutter: StaticTriangle2d<StaticPoint2d>,
+    /// Preset the scene was loaded from, `None` once it has been edited
+    pub preset: Option<&'static Preset>,
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self::from_preset(Preset::find("Default").expect("Default preset missing"))
+    }
+}
+
+impl From<&CutFailure> for Scene {
+    fn from(failure: &CutFailure) -> Self {
+        Scene {
+            triangle: failure.triangle,
+            cutter: failure.cutter,
+            preset: None,
+        }
+    }
+}
+
+/// Edit operation on a [`Scene`]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Command {
+    /// Moves one corner of the cut triangle
+    MoveCorner {
+        corner: usize,
+        to: StaticPoint2d,
+    },
+    LoadPreset(&'static Preset),
+    /// Transforms the cut triangle or the cutter as a whole
+    Transform {
+        shape: Shape,
+        transform: Affine,
+    },
+    /// Replaces the cut triangle or the cutter, e.g. by a transformed copy of a former one
+    SetShape {
+        shape: Shape,
+        to: StaticTriangle2d<StaticPoint2d>,
+    },
+    /// Reverses the corner order of the cut triangle or the cutter, keeping the first
+    /// corner in place
+    FlipOrientation(Shape),
+}
+
+/// One of the two triangles of a [`Scene`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Shape {
+    Triangle,
+    Cutter,
+}
+
+impl Scene {
+    pub fn from_preset(preset: &'static Preset) -> Self {
+        Self {
+            triangle: preset.triangle(),
+            cutter: preset.cutter(),
+            preset: Some(preset),
+        }
+    }
+
+    /// Returns the edited scene, or `None` if the command would produce an invalid scene
+    pub fn apply(&self, command: &Command) -> Option<Scene> {
+        match command {
+            Command::MoveCorner { corner, to } => {
+                let original = &self.triangle;
+                let pick = |idx: usize, pt: &StaticPoint2d| if idx == *corner { *to } else { *pt };
+                let moved = StaticTriangle2d::new(
+                    pick(0, original.p1()),
+                    pick(1, original.p2()),
+                    pick(2, original.p3()),
+                );
+                if keeps_orientation(original, &moved) {
+                    Some(Scene {
+                        triangle: moved,
+                        cutter: self.cutter,
+                        preset: None,
+                    })
+                } else {
+                    None
+                }
+            }
+            Command::LoadPreset(preset) => Some(Self::from_preset(preset)),
+            Command::Transform { shape, transform } => self.apply(&Command::SetShape {
+                shape: *shape,
+                to: transform.apply_triangle(self.shape(*shape)),
+            }),
+            Command::SetShape { shape, to } => {
+                // the cutter may change its orientation, e.g. when mirrored
+                let valid = match shape {
+                    Shape::Triangle => keeps_orientation(&self.triangle, to),
+                    Shape::Cutter => to.area() != Number::zero(),
+                };
+                let finite = to
+                    .points()
+                    .all(|pt| pt.x.0.is_finite() && pt.y.0.is_finite());
+                if !(valid && finite) {
+                    return None;
+                }
+                let mut scene = Scene {
+                    preset: None,
+                    ..*self
+                };
+                *scene.shape_mut(*shape) = *to;
+                Some(scene)
+            }
+            Command::FlipOrientation(shape) => {
+                let mut scene = Scene {
+                    preset: None,
+                    ..*self
+                };
+                let flipped = scene.shape_mut(*shape);
+                *flipped = StaticTriangle2d::new(*flipped.p1(), *flipped.p3(), *flipped.p2());
+                Some(scene)
+            }
+        }
+    }
+
+    pub fn shape(&self, shape: Shape) -> &StaticTriangle2d<StaticPoint2d> {
+        match shape {
+            Shape::Triangle => &self.triangle,
+            Shape::Cutter => &self.cutter,
+        }
+    }
+
+    fn shape_mut(&mut self, shape: Shape) -> &mut StaticTriangle2d<StaticPoint2d> {
+        match shape {
+            Shape::Triangle => &mut self.triangle,
+            Shape::Cutter => &mut self.cutter,
+        }
+    }
+
+    pub fn cut(&self) -> CutResult {
+        cut_triangle(&self.triangle, &self.cutter)
+    }
+
+    /// Like [`Scene::cut`], but reports panics of the cut instead of propagating them
+    pub fn guarded_cut(&self) -> Result<CutResult, CutFailure> {
+        guarded_cut(&self.triangle, &self.cutter)
+    }
+
+    /// Compares the cut result with the exact rational reference
+    pub fn precision_report(&self, result: &CutResult) -> PrecisionReport {
+        compare(&self.triangle, &self.cutter, result)
+    }
+
+    /// Figures showing the cut result, without any editing overlay
+    pub fn display_list(&self, result: &CutResult) -> Vec<Figure> {
+        [
+            SceneLayer::Vertices,
+            SceneLayer::Inside,
+            SceneLayer::Outside,
+        ]
+        .into_iter()
+        .flat_map(|layer| self.layer_figures(layer, result))
+        .collect()
+    }
+
+    pub fn layer_figures(&self, layer: SceneLayer, result: &CutResult) -> Vec<Figure> {
+        match layer {
+            SceneLayer::Input => vec![
+                Figure::polygon(
+                    CssStyle::Color(CssColor::Blue),
+                    self.triangle.to_any_polygon(),
+                ),
+                Figure::polygon(
+                    CssStyle::Color(CssColor::Black),
+                    self.cutter.to_any_polygon(),
+                ),
+            ],
+            SceneLayer::Vertices => self
+                .triangle
+                .points()
+                .map(|pt| Figure::marker(CssStyle::Color(CssColor::Blue), *pt))
+                .collect(),
+            SceneLayer::Annotations => self.annotations(),
+            SceneLayer::Inside => triangle_figures(&result.inside, CssColor::Green),
+            SceneLayer::Outside => triangle_figures(&result.outside, CssColor::Red),
+            SceneLayer::Precision => precision_figures(&self.precision_report(result)),
+            SceneLayer::Winding => [self.triangle, self.cutter]
+                .iter()
+                .chain(result.inside.iter())
+                .chain(result.outside.iter())
+                .flat_map(winding_indicator)
+                .collect(),
+        }
+    }
+}
+
+impl Scene {
+    /// Vertex names, winding direction of the cut triangle and the edge intersections
+    fn annotations(&self) -> Vec<Figure> {
+        let mut figures =
+            Figure::vertex_labels(CssStyle::Color(CssColor::Blue), "p", self.triangle.points());
+        figures.extend(Figure::vertex_labels(
+            CssStyle::Color(CssColor::Black),
+            "c",
+            self.cutter.points(),
+        ));
+        let triangle_edges = edges(&self.triangle);
+        for ((x1, y1), (x2, y2)) in triangle_edges {
+            let along =
+                |t: f64| -> StaticPoint2d { (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t).into() };
+            figures.push(Figure::arrow(
+                CssStyle::Color(CssColor::Blue),
+                along(0.4),
+                along(0.6),
+            ));
+        }
+        for triangle_edge in triangle_edges {
+            for cutter_edge in edges(&self.cutter) {
+                if let Some((x, y)) = segment_intersection(triangle_edge, cutter_edge) {
+                    let pt: StaticPoint2d = (x, y).into();
+                    figures.push(Figure::snap_marker(CssStyle::Color(CssColor::Black), pt));
+                    figures.push(Figure::label(
+                        CssStyle::Color(CssColor::Black),
+                        pt,
+                        format!("({x:.2}, {y:.2})"),
+                    ));
+                }
+            }
+        }
+        figures
+    }
+}
+
+/// Whether `to` has corners and winds the same way as `from`
+fn keeps_orientation(
+    from: &StaticTriangle2d<StaticPoint2d>,
+    to: &StaticTriangle2d<StaticPoint2d>,
+) -> bool {
+    let (from, to) = (from.area(), to.area());
+    !to.is_zero() && (from > Number::zero()) == (to > Number::zero())
+}
+
+/// Circular arrow around the centroid turning the way the corners wind, blue for
+/// counter-clockwise and red for clockwise triangles
+fn winding_indicator(triangle: &StaticTriangle2d<StaticPoint2d>) -> Vec<Figure> {
+    let metrics = TriangleMetrics::of(triangle);
+    let (color, clockwise) = match metrics.orientation {
+        Orientation::CounterClockwise => (CssColor::Blue, false),
+        Orientation::Clockwise => (CssColor::Red, true),
+        Orientation::Degenerate => return Vec::new(),
+    };
+    let center = centroid(triangle);
+    // half the radius of the inscribed circle, which is twice the area over the
+    // perimeter, keeps the arc around the centroid inside the triangle
+    let radius = metrics.area() / metrics.perimeter;
+    let (start, end) = (FRAC_PI_2, FRAC_PI_2 + 1.5 * PI);
+    let at = |angle: f64| -> StaticPoint2d {
+        (
+            center.x.0 + radius * angle.cos(),
+            center.y.0 + radius * angle.sin(),
+        )
+            .into()
+    };
+    // the head sits where the arc ends when running the way the corners wind
+    let head = if clockwise {
+        Figure::arrow(CssStyle::Color(color.clone()), at(start + 0.1), at(start))
+    } else {
+        Figure::arrow(CssStyle::Color(color.clone()), at(end - 0.1), at(end))
+    };
+    vec![
+        Figure::arc(CssStyle::Color(color), center, radius, start, end),
+        head,
+    ]
+}
+
+fn edges(triangle: &StaticTriangle2d<StaticPoint2d>) -> [Segment; 3] {
+    let [p1, p2, p3] = [triangle.p1(), triangle.p2(), triangle.p3()].map(|p| {
+        let StaticPoint2d { x, y } = p.coordinates();
+        (x.0, y.0)
+    });
+    [(p1, p2), (p2, p3), (p3, p1)]
+}
+
+/// Groups of figures derived from a [`Scene`]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SceneLayer {
+    /// Outlines of the cut triangle and the cutter
+    Input,
+    /// Corners of the cut triangle
+    Vertices,
+    /// Vertex names, winding arrows and edge intersection points
+    Annotations,
+    /// Cut result covered by the cutter
+    Inside,
+    /// Cut result outside the cutter
+    Outside,
+    /// Differences to the exact rational reference: its outline, moved and missing
+    /// corners and triangles on the wrong side of the cutter
+    Precision,
+    /// Turning direction of the corners of the input and of every output triangle
+    Winding,
+}
+
+fn triangle_figures(triangles: &[StaticTriangle2d<StaticPoint2d>], color: CssColor) -> Vec<Figure> {
+    triangles
+        .iter()
+        .map(|triangle| Figure::polygon(CssStyle::Color(color.clone()), triangle.to_any_polygon()))
+        .collect()
+}
+
+/// Figures of [`SceneLayer::Precision`] for an already computed report
+pub fn precision_figures(report: &PrecisionReport) -> Vec<Figure> {
+    let mut figures = Vec::new();
+    if let Some(first) = report.exact.polygon.first() {
+        let mut outline = report.exact.polygon.clone();
+        outline.push(*first);
+        figures.push(Figure::lines(CssStyle::Color(CssColor::Black), outline));
+    }
+    figures.extend(triangle_figures(
+        &report.misclassified_triangles,
+        CssColor::Red,
+    ));
+    figures.extend(triangle_figures(&report.appeared_triangles, CssColor::Red));
+    for (pt, distance) in &report.moved_points {
+        figures.push(Figure::snap_marker(CssStyle::Color(CssColor::Red), *pt));
+        figures.push(Figure::label(
+            CssStyle::Color(CssColor::Red),
+            *pt,
+            format!("{distance:.1e}"),
+        ));
+    }
+    figures.extend(
+        report
+            .missing_points
+            .iter()
+            .map(|pt| Figure::marker(CssStyle::Color(CssColor::Red), *pt)),
+    );
+    figures
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::{Point2d, StaticPoint2d, Triangle2d};
+
+    use crate::geometry::presets::Preset;
+    use crate::geometry::transform::{centroid, Affine};
+    use crate::scene::{Command, Scene, SceneLayer, Shape};
+
+    #[test]
+    fn test_move_corner() {
+        let scene = Scene::default();
+        let moved = scene
+            .apply(&Command::MoveCorner {
+                corner: 2,
+                to: (0.0, 50.0).into(),
+            })
+            .unwrap();
+        assert_eq!(
+            moved.triangle.p3().coordinates(),
+            StaticPoint2d::from((0.0, 50.0))
+        );
+        assert_eq!(moved.triangle.p1(), scene.triangle.p1());
+        assert_eq!(moved.preset, None);
+
+        // moving the top corner below the base would flip the triangle
+        let flipped = scene.apply(&Command::MoveCorner {
+            corner: 2,
+            to: (0.0, -50.0).into(),
+        });
+        assert_eq!(flipped, None);
+
+        let reloaded = moved
+            .apply(&Command::LoadPreset(Preset::find("Default").unwrap()))
+            .unwrap();
+        assert_eq!(reloaded, scene);
+    }
+
+    #[test]
+    fn test_transform() {
+        let scene = Scene::default();
+        let center = centroid(&scene.cutter);
+        let rotated = scene
+            .apply(&Command::Transform {
+                shape: Shape::Cutter,
+                transform: Affine::rotation(&center, 180.0),
+            })
+            .unwrap();
+        assert_eq!(rotated.triangle, scene.triangle);
+        assert_ne!(rotated.cutter, scene.cutter);
+
+        // mirroring flips the cut triangle to clockwise corners
+        let mirror = Affine::mirror_x(&centroid(&scene.triangle));
+        let shape = |shape| Command::Transform {
+            shape,
+            transform: mirror,
+        };
+        assert_eq!(scene.apply(&shape(Shape::Triangle)), None);
+        assert!(scene.apply(&shape(Shape::Cutter)).is_some());
+        let collapsed = Command::Transform {
+            shape: Shape::Cutter,
+            transform: Affine::scaling(&center, 0.0),
+        };
+        assert_eq!(scene.apply(&collapsed), None);
+    }
+
+    #[test]
+    fn test_flip_orientation() {
+        let scene = Scene::default();
+        let flipped = scene
+            .apply(&Command::FlipOrientation(Shape::Triangle))
+            .unwrap();
+        assert_eq!(flipped.triangle.p1(), scene.triangle.p1());
+        assert_eq!(flipped.triangle.area().0, -scene.triangle.area().0);
+
+        // edits keep the clockwise corners of the flipped triangle
+        let moved = flipped.apply(&Command::MoveCorner {
+            corner: 1,
+            to: (0.0, 50.0).into(),
+        });
+        assert!(moved.is_some_and(|moved| moved.triangle.area().0 < 0.0));
+        let mirror = Command::Transform {
+            shape: Shape::Triangle,
+            transform: Affine::mirror_x(&centroid(&flipped.triangle)),
+        };
+        assert_eq!(flipped.apply(&mirror), None);
+
+        let result = scene.cut();
+        let indicators = scene.layer_figures(SceneLayer::Winding, &result);
+        assert_eq!(
+            indicators.len(),
+            2 * (2 + result.inside.len() + result.outside.len())
+        );
+    }
+}
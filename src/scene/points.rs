@@ -0,0 +1,368 @@
+use triangles::prelude::{Polygon2d, StaticPoint2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::delaunay::{circumcircle, triangulate, Triangulation};
+use crate::geometry::quality::{Grade, TriangleQuality};
+use crate::geometry::snap::{SnapModes, SnapTarget, Snapper};
+use crate::scene::editor::{pick_point, PointerInput, PICK_RADIUS};
+
+/// Input of the triangulation page: free points and edges between them which have to
+/// be part of the triangulation
+#[derive(Clone, PartialEq, Debug)]
+pub struct PointSet {
+    pub points: Vec<StaticPoint2d>,
+    /// Pairs of indices into `points`
+    pub constraints: Vec<(usize, usize)>,
+}
+
+impl Default for PointSet {
+    fn default() -> Self {
+        Self {
+            points: [
+                (0.0, 0.0),
+                (40.0, -10.0),
+                (80.0, 10.0),
+                (70.0, 60.0),
+                (30.0, 70.0),
+                (-10.0, 40.0),
+                (35.0, 25.0),
+            ]
+            .into_iter()
+            .map(StaticPoint2d::from)
+            .collect(),
+            constraints: Vec::new(),
+        }
+    }
+}
+
+/// Edit operation on a [`PointSet`]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PointCommand {
+    Add(StaticPoint2d),
+    Move { index: usize, to: StaticPoint2d },
+    Remove(usize),
+    Connect(usize, usize),
+}
+
+/// Parts of the triangulation display which can be switched on and off
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TriangulationLayers {
+    /// Shows [`PointSet::circumcircle_figures`], which are not part of
+    /// [`PointSet::figures`]
+    pub circumcircles: bool,
+    pub quality: bool,
+}
+
+impl Default for TriangulationLayers {
+    fn default() -> Self {
+        Self {
+            circumcircles: false,
+            quality: true,
+        }
+    }
+}
+
+impl PointSet {
+    pub fn apply(&self, command: &PointCommand) -> PointSet {
+        let mut next = self.clone();
+        match *command {
+            PointCommand::Add(pt) => next.points.push(pt),
+            PointCommand::Move { index, to } => {
+                if let Some(pt) = next.points.get_mut(index) {
+                    *pt = to;
+                }
+            }
+            PointCommand::Remove(index) => {
+                if index < next.points.len() {
+                    next.points.remove(index);
+                    let shift = |idx: usize| if idx > index { idx - 1 } else { idx };
+                    next.constraints = self
+                        .constraints
+                        .iter()
+                        .filter(|(a, b)| *a != index && *b != index)
+                        .map(|(a, b)| (shift(*a), shift(*b)))
+                        .collect();
+                }
+            }
+            PointCommand::Connect(a, b) => {
+                let exists = next
+                    .constraints
+                    .iter()
+                    .any(|edge| *edge == (a, b) || *edge == (b, a));
+                if a != b && a < next.points.len() && b < next.points.len() && !exists {
+                    next.constraints.push((a, b));
+                }
+            }
+        }
+        next
+    }
+
+    pub fn triangulate(&self) -> Triangulation {
+        triangulate(&self.points, &self.constraints)
+    }
+
+    pub fn figures(
+        &self,
+        triangulation: &Triangulation,
+        layers: TriangulationLayers,
+    ) -> Vec<Figure> {
+        let mut figures = Vec::new();
+        for triangle in triangulation.to_triangles(&self.points) {
+            let color = if layers.quality {
+                grade_color(TriangleQuality::of(&triangle).grade())
+            } else {
+                CssColor::Black
+            };
+            figures.push(Figure::polygon(
+                CssStyle::Color(color),
+                triangle.to_any_polygon(),
+            ));
+        }
+        let edge = |color: CssColor, (a, b): &(usize, usize)| {
+            Figure::lines(
+                CssStyle::Color(color),
+                vec![self.points[*a], self.points[*b]],
+            )
+        };
+        figures.extend(
+            triangulation
+                .constrained
+                .iter()
+                .map(|e| edge(CssColor::Blue, e)),
+        );
+        figures.extend(
+            triangulation
+                .rejected
+                .iter()
+                .map(|e| edge(CssColor::Red, e)),
+        );
+        figures.extend(
+            self.points
+                .iter()
+                .map(|pt| Figure::marker(CssStyle::Color(CssColor::Blue), *pt)),
+        );
+        figures
+    }
+
+    /// Circumcircles of the triangles. Nearly collinear triangles have huge ones, so they
+    /// belong in a layer the view is not fitted to.
+    pub fn circumcircle_figures(&self, triangulation: &Triangulation) -> Vec<Figure> {
+        triangulation
+            .triangles
+            .iter()
+            .filter_map(|corners| {
+                let [a, b, c] = corners.map(|idx| self.points[idx]);
+                circumcircle(&a, &b, &c)
+            })
+            .map(|(center, radius)| {
+                Figure::circle(CssStyle::Color(CssColor::Black), center, radius)
+            })
+            .collect()
+    }
+}
+
+/// Color for triangles of the given quality
+pub fn grade_color(grade: Grade) -> CssColor {
+    match grade {
+        Grade::Good => CssColor::Green,
+        Grade::Fair => CssColor::Blue,
+        Grade::Poor => CssColor::Red,
+    }
+}
+
+/// What a click on the canvas does
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum PointTool {
+    /// Adds a point on empty space, drags existing points
+    #[default]
+    Place,
+    /// Connects two consecutively clicked points with a constraint edge
+    Connect,
+    Remove,
+}
+
+/// Interaction state for editing a [`PointSet`]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct PointEditor {
+    pub tool: PointTool,
+    hovered: Option<usize>,
+    dragged: Option<usize>,
+    /// First point of a constraint edge being connected
+    pending: Option<usize>,
+    pressed: bool,
+    snap: Option<SnapTarget>,
+}
+
+impl PointEditor {
+    pub fn pointer(&mut self, set: &PointSet, input: &PointerInput) -> Option<PointCommand> {
+        let press_started = input.pressed && !self.pressed;
+        self.pressed = input.pressed;
+        if !input.pressed {
+            self.dragged = None;
+            self.snap = None;
+        }
+        if let Some(index) = self.dragged {
+            self.snap = self.snapped(input);
+            let to = self.snap.map(|s| s.point).unwrap_or(input.position);
+            return Some(PointCommand::Move { index, to });
+        }
+        self.hovered = pick_point(&set.points, input);
+        if !press_started {
+            return None;
+        }
+        match (self.tool, self.hovered) {
+            (PointTool::Place, Some(index)) => {
+                self.dragged = Some(index);
+                None
+            }
+            (PointTool::Place, None) => {
+                let snap = self.snapped(input);
+                self.dragged = Some(set.points.len());
+                self.hovered = self.dragged;
+                Some(PointCommand::Add(
+                    snap.map(|s| s.point).unwrap_or(input.position),
+                ))
+            }
+            (PointTool::Connect, Some(index)) => match self.pending.take() {
+                Some(first) if first != index => Some(PointCommand::Connect(first, index)),
+                Some(_) => None,
+                None => {
+                    self.pending = Some(index);
+                    None
+                }
+            },
+            (PointTool::Connect, None) => {
+                self.pending = None;
+                None
+            }
+            (PointTool::Remove, Some(index)) => {
+                self.hovered = None;
+                Some(PointCommand::Remove(index))
+            }
+            (PointTool::Remove, None) => None,
+        }
+    }
+
+    /// Switches the tool, dropping a half finished constraint edge
+    pub fn select_tool(&mut self, tool: PointTool) {
+        self.tool = tool;
+        self.pending = None;
+    }
+
+    /// Hover, pending constraint and snap markers to draw on top of the point set
+    pub fn overlay(&self, set: &PointSet) -> Vec<Figure> {
+        let mut figures = Vec::new();
+        if let Some(pt) = self.hovered.and_then(|idx| set.points.get(idx)) {
+            let color = match self.tool {
+                PointTool::Remove => CssColor::Red,
+                PointTool::Place | PointTool::Connect => CssColor::Green,
+            };
+            figures.push(Figure::marker(CssStyle::Color(color), *pt));
+        }
+        if let Some(pt) = self.pending.and_then(|idx| set.points.get(idx)) {
+            figures.push(Figure::snap_marker(CssStyle::Color(CssColor::Blue), *pt));
+        }
+        if let Some(snap) = self.snap {
+            figures.push(Figure::snap_marker(
+                CssStyle::Color(CssColor::Red),
+                snap.point,
+            ));
+        }
+        figures
+    }
+
+    fn snapped(&self, input: &PointerInput) -> Option<SnapTarget> {
+        if input.snapping_disabled {
+            return None;
+        }
+        // snapping onto other points would create duplicates, only the grid is useful here
+        let modes = SnapModes {
+            grid: true,
+            integer: false,
+            vertices: false,
+            edges: false,
+            midpoints: false,
+            intersections: false,
+        };
+        Snapper::new(modes, input.resolution * PICK_RADIUS, input.grid_step).snap(&input.position)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticPoint2d;
+
+    use crate::scene::editor::PointerInput;
+    use crate::scene::points::{
+        PointCommand, PointEditor, PointSet, PointTool, TriangulationLayers,
+    };
+
+    fn input(x: f64, y: f64, pressed: bool) -> PointerInput {
+        PointerInput {
+            position: (x, y).into(),
+            pressed,
+            resolution: 0.5,
+            grid_step: (10.0, 10.0),
+            snapping_disabled: false,
+        }
+    }
+
+    #[test]
+    fn test_remove_point() {
+        let set = PointSet::default()
+            .apply(&PointCommand::Connect(0, 2))
+            .apply(&PointCommand::Connect(3, 5))
+            .apply(&PointCommand::Connect(5, 3));
+        assert_eq!(set.constraints, vec![(0, 2), (3, 5)]);
+        let removed = set.apply(&PointCommand::Remove(2));
+        assert_eq!(removed.points.len(), set.points.len() - 1);
+        assert_eq!(removed.constraints, vec![(2, 4)]);
+    }
+
+    #[test]
+    fn test_place_and_connect() {
+        let mut set = PointSet::default();
+        let mut editor = PointEditor::default();
+        let count = set.points.len();
+
+        // clicking empty space adds a grid snapped point which follows the mouse
+        let command = editor.pointer(&set, &input(101.0, 99.0, true)).unwrap();
+        assert_eq!(
+            command,
+            PointCommand::Add(StaticPoint2d::from((100.0, 100.0)))
+        );
+        set = set.apply(&command);
+        let command = editor.pointer(&set, &input(111.0, 99.0, true)).unwrap();
+        assert_eq!(
+            command,
+            PointCommand::Move {
+                index: count,
+                to: (110.0, 100.0).into()
+            }
+        );
+        set = set.apply(&command);
+        assert_eq!(editor.pointer(&set, &input(111.0, 99.0, false)), None);
+
+        editor.select_tool(PointTool::Connect);
+        editor.pointer(&set, &input(0.0, 0.0, true));
+        editor.pointer(&set, &input(0.0, 0.0, false));
+        let command = editor.pointer(&set, &input(110.0, 101.0, true));
+        assert_eq!(command, Some(PointCommand::Connect(0, count)));
+    }
+
+    #[test]
+    fn test_circumcircle_figures() {
+        let set = PointSet::default();
+        let triangulation = set.triangulate();
+        let layers = TriangulationLayers {
+            circumcircles: true,
+            quality: false,
+        };
+        // the circles come separately, the figures keep the extent of the points
+        let circles = set.circumcircle_figures(&triangulation);
+        assert!(!circles.is_empty());
+        assert_eq!(circles.len(), triangulation.triangles.len());
+        let figures = set.figures(&triangulation, layers);
+        assert!(figures.iter().all(|figure| !circles.contains(figure)));
+    }
+}
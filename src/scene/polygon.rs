@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{Polygon2d, StaticPoint2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::ear_clipping::{polygon_area, triangulate, EarClipping};
+use crate::geometry::quality::TriangleQuality;
+use crate::geometry::snap::{SnapModes, SnapTarget, Snapper};
+use crate::scene::editor::{pick_point, PointerInput, PICK_RADIUS};
+use crate::scene::points::grade_color;
+
+/// Input of the ear clipping page: an outer ring followed by the holes
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PolygonScene {
+    #[serde(with = "crate::geometry::coordinates")]
+    pub rings: Vec<Vec<StaticPoint2d>>,
+}
+
+impl Default for PolygonScene {
+    fn default() -> Self {
+        let ring =
+            |coords: &[(f64, f64)]| coords.iter().copied().map(StaticPoint2d::from).collect();
+        Self {
+            rings: vec![
+                ring(&[
+                    (0.0, 0.0),
+                    (60.0, 0.0),
+                    (60.0, 50.0),
+                    (40.0, 30.0),
+                    (20.0, 50.0),
+                    (0.0, 40.0),
+                ]),
+                ring(&[(10.0, 10.0), (25.0, 10.0), (15.0, 25.0)]),
+            ],
+        }
+    }
+}
+
+/// Edit operation on a [`PolygonScene`]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PolygonCommand {
+    /// Appends a vertex to the last ring
+    AddVertex(StaticPoint2d),
+    MoveVertex {
+        ring: usize,
+        index: usize,
+        to: StaticPoint2d,
+    },
+    /// Starts a new hole, following vertices are added to it
+    StartHole,
+    Clear,
+}
+
+impl PolygonScene {
+    pub fn outer(&self) -> &[StaticPoint2d] {
+        self.rings.first().map(Vec::as_slice).unwrap_or_default()
+    }
+    pub fn holes(&self) -> &[Vec<StaticPoint2d>] {
+        self.rings.get(1..).unwrap_or_default()
+    }
+
+    pub fn apply(&self, command: &PolygonCommand) -> PolygonScene {
+        let mut next = self.clone();
+        match *command {
+            PolygonCommand::AddVertex(pt) => match next.rings.last_mut() {
+                Some(ring) => ring.push(pt),
+                None => next.rings.push(vec![pt]),
+            },
+            PolygonCommand::MoveVertex { ring, index, to } => {
+                if let Some(pt) = next.rings.get_mut(ring).and_then(|r| r.get_mut(index)) {
+                    *pt = to;
+                }
+            }
+            PolygonCommand::StartHole => {
+                if next.rings.last().is_some_and(|ring| ring.len() >= 3) {
+                    next.rings.push(Vec::new());
+                }
+            }
+            PolygonCommand::Clear => next.rings = vec![Vec::new()],
+        }
+        next
+    }
+
+    pub fn triangulate(&self) -> EarClipping {
+        triangulate(self.outer(), self.holes())
+    }
+
+    pub fn area(&self) -> f64 {
+        polygon_area(self.outer(), self.holes())
+    }
+
+    /// Triangles clipped up to and including `step`, all of them for `None`, followed by
+    /// the polygon outline and the ring left over after that step
+    pub fn figures(
+        &self,
+        clipping: &EarClipping,
+        step: Option<usize>,
+        quality: bool,
+    ) -> Vec<Figure> {
+        let shown = step.map_or(clipping.ears.len(), |step| {
+            (step + 1).min(clipping.ears.len())
+        });
+        let mut figures = Vec::new();
+        for ear in &clipping.ears[..shown] {
+            let triangle = clipping.triangle(ear);
+            let color = if quality {
+                grade_color(TriangleQuality::of(&triangle).grade())
+            } else {
+                CssColor::Green
+            };
+            figures.push(Figure::polygon(
+                CssStyle::Color(color),
+                triangle.to_any_polygon(),
+            ));
+        }
+        for ring in self.rings.iter().filter(|ring| !ring.is_empty()) {
+            let mut outline = ring.clone();
+            outline.push(ring[0]);
+            figures.push(Figure::lines(CssStyle::Color(CssColor::Black), outline));
+            figures.extend(
+                ring.iter()
+                    .map(|pt| Figure::marker(CssStyle::Color(CssColor::Black), *pt)),
+            );
+        }
+        if let Some(ear) = step.and_then(|step| clipping.ears.get(step)) {
+            let mut remaining: Vec<StaticPoint2d> = ear
+                .remaining
+                .iter()
+                .map(|idx| clipping.vertices[*idx])
+                .collect();
+            if let Some(first) = remaining.first().copied() {
+                remaining.push(first);
+                figures.push(Figure::lines(CssStyle::Color(CssColor::Blue), remaining));
+            }
+            figures.push(Figure::snap_marker(
+                CssStyle::Color(CssColor::Red),
+                clipping.vertices[ear.corners[1]],
+            ));
+        }
+        figures
+    }
+}
+
+/// Interaction state for drawing a [`PolygonScene`]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct PolygonEditor {
+    /// Ring and vertex index
+    hovered: Option<(usize, usize)>,
+    dragged: Option<(usize, usize)>,
+    pressed: bool,
+    snap: Option<SnapTarget>,
+}
+
+impl PolygonEditor {
+    /// Pressing on a vertex drags it, pressing anywhere else adds a vertex to the last ring
+    pub fn pointer(
+        &mut self,
+        scene: &PolygonScene,
+        input: &PointerInput,
+    ) -> Option<PolygonCommand> {
+        let press_started = input.pressed && !self.pressed;
+        self.pressed = input.pressed;
+        if !input.pressed {
+            self.dragged = None;
+        }
+        self.snap = if input.pressed && !input.snapping_disabled {
+            snapper(scene, input, self.dragged).snap(&input.position)
+        } else {
+            None
+        };
+        let position = self.snap.map(|s| s.point).unwrap_or(input.position);
+        if let Some((ring, index)) = self.dragged {
+            return Some(PolygonCommand::MoveVertex {
+                ring,
+                index,
+                to: position,
+            });
+        }
+        let vertices: Vec<(usize, usize)> = scene
+            .rings
+            .iter()
+            .enumerate()
+            .flat_map(|(ring, points)| (0..points.len()).map(move |index| (ring, index)))
+            .collect();
+        self.hovered = pick_point(scene.rings.iter().flatten(), input).map(|idx| vertices[idx]);
+        if !press_started {
+            return None;
+        }
+        match self.hovered {
+            Some(vertex) => {
+                self.dragged = Some(vertex);
+                None
+            }
+            None => Some(PolygonCommand::AddVertex(position)),
+        }
+    }
+
+    pub fn overlay(&self, scene: &PolygonScene) -> Vec<Figure> {
+        let mut figures = Vec::new();
+        if let Some(pt) = self
+            .hovered
+            .and_then(|(ring, index)| scene.rings.get(ring)?.get(index))
+        {
+            figures.push(Figure::marker(CssStyle::Color(CssColor::Green), *pt));
+        }
+        if let Some(snap) = self.snap {
+            figures.push(Figure::snap_marker(
+                CssStyle::Color(CssColor::Red),
+                snap.point,
+            ));
+        }
+        figures
+    }
+}
+
+/// New and dragged vertices snap to the grid and the other polygon vertices
+fn snapper(scene: &PolygonScene, input: &PointerInput, dragged: Option<(usize, usize)>) -> Snapper {
+    let modes = SnapModes {
+        grid: true,
+        integer: false,
+        vertices: true,
+        edges: false,
+        midpoints: false,
+        intersections: false,
+    };
+    Snapper::new(modes, input.resolution * PICK_RADIUS, input.grid_step).with_vertices(
+        scene.rings.iter().enumerate().flat_map(|(ring, points)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(move |(index, _)| dragged != Some((ring, *index)))
+                .map(|(_, pt)| pt)
+        }),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticPoint2d;
+
+    use crate::scene::polygon::{PolygonCommand, PolygonScene};
+
+    #[test]
+    fn test_draw_holes() {
+        let scene = PolygonScene::default()
+            .apply(&PolygonCommand::Clear)
+            .apply(&PolygonCommand::StartHole);
+        assert_eq!(scene.rings, vec![Vec::new()]);
+        let scene = [(0.0, 0.0), (40.0, 0.0), (0.0, 40.0)]
+            .into_iter()
+            .fold(scene, |scene, pt| {
+                scene.apply(&PolygonCommand::AddVertex(StaticPoint2d::from(pt)))
+            })
+            .apply(&PolygonCommand::StartHole);
+        let scene = [(5.0, 5.0), (15.0, 5.0), (5.0, 15.0)]
+            .into_iter()
+            .fold(scene, |scene, pt| {
+                scene.apply(&PolygonCommand::AddVertex(StaticPoint2d::from(pt)))
+            });
+        assert_eq!(scene.holes().len(), 1);
+        assert_eq!(scene.area(), 800.0 - 50.0);
+        let clipping = scene.triangulate();
+        assert!(clipping.complete);
+        assert!((clipping.triangle_area() - scene.area()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,247 @@
+//! Reproductions of a scene to paste into a bug report or the `triangles` test suite.
+
+use std::fmt::Write;
+
+use triangles::prelude::{Point2d, StaticPoint2d, StaticTriangle2d, Triangle2d};
+
+use crate::geometry::cut::CutResult;
+use crate::geometry::exact::RELATIVE_TOLERANCE;
+use crate::scene::file::SceneFile;
+use crate::scene::Scene;
+
+/// The scene in the format of the scene files
+pub fn scene_json(scene: &Scene) -> String {
+    SceneFile::from(scene).to_json()
+}
+
+/// A test cutting the triangle of `scene` with its cutter, which fails as long as the
+/// cut panics
+pub fn panic_test(scene: &Scene) -> String {
+    let mut out = test_header(scene);
+    writeln!(out, "    let _ = triangle.cut_to_triangles(&cutter);").unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// A test cutting the triangle of `scene` with its cutter and asserting the counts and
+/// areas of `result`, the output observed so far
+pub fn cut_test(scene: &Scene, result: &CutResult) -> String {
+    let mut out = test_header(scene);
+    writeln!(
+        out,
+        "    let [inside, outside] = triangle.cut_to_triangles(&cutter).map(|side| {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        side.iter().map(|t| t.coordinates_triangle()).collect::<Vec<_>>()"
+    )
+    .unwrap();
+    writeln!(out, "    }});").unwrap();
+    writeln!(
+        out,
+        "    let assert_area = |triangles: &[StaticTriangle2d<StaticPoint2d>], expected: f64| {{"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        let area: f64 = triangles.iter().map(|t| t.area().0.abs()).sum();"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        let tolerance = {} * expected.abs().max(1.0);",
+        literal(RELATIVE_TOLERANCE)
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        assert!((area - expected).abs() <= tolerance, \"area {{area}}, expected {{expected}}\");"
+    )
+    .unwrap();
+    writeln!(out, "    }};").unwrap();
+    writeln!(
+        out,
+        "    assert_eq!(inside.len(), {});",
+        result.inside.len()
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    assert_eq!(outside.len(), {});",
+        result.outside.len()
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    assert_area(&inside, {});",
+        literal(result.inside_area())
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "    assert_area(&outside, {});",
+        literal(result.outside_area())
+    )
+    .unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// Start of a test function up to the construction of the inputs
+fn test_header(scene: &Scene) -> String {
+    let mut out = String::new();
+    writeln!(out, "#[test]").unwrap();
+    writeln!(out, "fn {}() {{", test_name(scene)).unwrap();
+    writeln!(
+        out,
+        "    use triangles::prelude::{{StaticPoint2d, StaticTriangle2d, Triangle2d}};"
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    write_triangle(&mut out, "triangle", &scene.triangle);
+    write_triangle(&mut out, "cutter", &scene.cutter);
+    out
+}
+
+/// Name of the test for `scene`, which differs between scenes so that several
+/// reproductions can be pasted into one file
+fn test_name(scene: &Scene) -> String {
+    // FNV-1a over the coordinate bits, which stays the same across builds
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for triangle in [&scene.triangle, &scene.cutter] {
+        for p in [triangle.p1(), triangle.p2(), triangle.p3()] {
+            let StaticPoint2d { x, y } = p.coordinates();
+            for byte in [x.0, y.0]
+                .iter()
+                .flat_map(|value| value.to_bits().to_le_bytes())
+            {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+    format!("test_cut_reproduction_{:08x}", hash >> 32)
+}
+
+fn write_triangle(out: &mut String, name: &str, triangle: &StaticTriangle2d<StaticPoint2d>) {
+    writeln!(out, "    let {name} = StaticTriangle2d::new(").unwrap();
+    for p in [triangle.p1(), triangle.p2(), triangle.p3()] {
+        let StaticPoint2d { x, y } = p.coordinates();
+        writeln!(
+            out,
+            "        StaticPoint2d::from(({}, {})),",
+            literal(x.0),
+            literal(y.0)
+        )
+        .unwrap();
+    }
+    writeln!(out, "    );").unwrap();
+}
+
+/// Rust expression for `value` which parses back to exactly the same bits
+fn literal(value: f64) -> String {
+    if value.is_nan() {
+        "f64::NAN".to_string()
+    } else if value.is_infinite() {
+        format!("{}f64::INFINITY", if value < 0.0 { "-" } else { "" })
+    } else {
+        // the debug format is the shortest representation which round trips
+        format!("{value:?}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::StaticTriangle2d;
+
+    use crate::geometry::cut::{cut_triangle, CutResult};
+    use crate::scene::reproduction::{cut_test, literal, panic_test, test_name};
+    use crate::scene::{Command, Scene};
+
+    // the emitted test for `contained_scene`, compiled and run along with these tests
+    include!("../../snapshots/reproduction/contained.rs");
+
+    /// Triangle lying within the cutter, which comes out of the cut unchanged
+    fn contained_scene() -> Scene {
+        Scene {
+            triangle: StaticTriangle2d::new(
+                (0.0, 0.0).into(),
+                (1.0, 0.0).into(),
+                (0.0, 1.0).into(),
+            ),
+            cutter: StaticTriangle2d::new(
+                (-10.0, -10.0).into(),
+                (30.0, -10.0).into(),
+                (-10.0, 30.0).into(),
+            ),
+            preset: None,
+        }
+    }
+
+    #[test]
+    fn test_literal() {
+        for value in [0.1 + 0.2, -1e-300, 12345.678, 1.0 / 3.0, f64::MAX] {
+            assert_eq!(
+                literal(value).parse::<f64>().unwrap().to_bits(),
+                value.to_bits()
+            );
+        }
+        assert_eq!(literal(100.0), "100.0");
+        assert_eq!(literal(f64::NEG_INFINITY), "-f64::INFINITY");
+    }
+
+    #[test]
+    fn test_panic_test() {
+        let code = panic_test(&Scene::default());
+        assert!(code.starts_with("#[test]\nfn test_cut_reproduction_"));
+        assert!(code.contains("        StaticPoint2d::from((-100.0, 0.0)),\n"));
+        assert!(code.contains("    let _ = triangle.cut_to_triangles(&cutter);\n"));
+    }
+
+    #[test]
+    fn test_cut_test() {
+        let scene = Scene::default();
+        let result = CutResult {
+            inside: vec![StaticTriangle2d::new(
+                (0.0, 0.0).into(),
+                (0.1 + 0.2, 0.0).into(),
+                (0.0, 1.0).into(),
+            )],
+            outside: Vec::new(),
+        };
+        let code = cut_test(&scene, &result);
+        assert!(code.contains("    assert_eq!(inside.len(), 1);\n"));
+        assert!(code.contains("    assert_eq!(outside.len(), 0);\n"));
+        assert!(code.contains("    assert_area(&inside, 0.15000000000000002);\n"));
+        assert!(code.contains("    assert_area(&outside, 0.0);\n"));
+        assert!(code.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_cut_test_compiles() {
+        let scene = contained_scene();
+        let result = CutResult {
+            inside: vec![scene.triangle],
+            outside: Vec::new(),
+        };
+        assert_eq!(cut_triangle(&scene.triangle, &scene.cutter), result);
+        assert_eq!(
+            cut_test(&scene, &result),
+            include_str!("../../snapshots/reproduction/contained.rs")
+        );
+    }
+
+    #[test]
+    fn test_test_name() {
+        let scene = Scene::default();
+        let moved = scene
+            .apply(&Command::MoveCorner {
+                corner: 2,
+                to: (0.0, 60.0).into(),
+            })
+            .unwrap();
+        assert_eq!(test_name(&scene), test_name(&Scene::default()));
+        assert_ne!(test_name(&scene), test_name(&moved));
+        assert_ne!(test_name(&scene), test_name(&contained_scene()));
+    }
+}
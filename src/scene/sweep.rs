@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use triangles::prelude::{BoundingBox, BoundingBoxValues, Polygon2d, StaticPoint2d, Triangle2d};
+
+use crate::display::{CssColor, CssStyle, Figure};
+use crate::geometry::robustness::{check_cut, Violations};
+use crate::scene::{Command, Scene};
+
+/// Invariant violations of the cut while one corner of the cut triangle moves over a
+/// grid of cells
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Sweep {
+    /// Scene the corner was moved in
+    #[serde(with = "crate::geometry::coordinates")]
+    pub scene: Scene,
+    /// Index of the moved corner
+    pub corner: usize,
+    #[serde(with = "crate::geometry::coordinates")]
+    pub bounds: BoundingBoxValues,
+    /// Cells per row and per column
+    pub steps: usize,
+    /// Cells checked so far, row by row from the lower left cell, `None` where the
+    /// corner would flip the triangle, which the scene does not allow
+    pub cells: Vec<Option<Violations>>,
+}
+
+/// Number of swept positions with each kind of violation
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct SweepCounts {
+    pub checked: usize,
+    pub panicked: usize,
+    pub area_mismatch: usize,
+    pub degenerate_output: usize,
+}
+
+impl Sweep {
+    /// Region around both triangles, large enough to move the corner past the cutter
+    pub fn region(scene: &Scene) -> Option<BoundingBoxValues> {
+        let mut bbox = BoundingBox::default();
+        for pt in scene.triangle.points().chain(scene.cutter.points()) {
+            bbox += *pt;
+        }
+        match bbox {
+            BoundingBox::Box(bounds) => Some(bounds.expand(0.2.into())),
+            BoundingBox::Empty => None,
+        }
+    }
+
+    /// Sweep of `corner` over `steps` by `steps` cells covering `bounds`, with no cell
+    /// checked yet
+    pub fn new(scene: &Scene, corner: usize, bounds: BoundingBoxValues, steps: usize) -> Self {
+        Self {
+            scene: *scene,
+            corner,
+            bounds,
+            steps: steps.max(1),
+            cells: Vec::new(),
+        }
+    }
+
+    /// Moves `corner` to the center of each cell and checks the cut there
+    pub fn run(scene: &Scene, corner: usize, bounds: BoundingBoxValues, steps: usize) -> Self {
+        let mut sweep = Self::new(scene, corner, bounds, steps);
+        sweep.cells = (0..sweep.cell_count())
+            .map(|index| sweep.check(index))
+            .collect();
+        sweep
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.steps * self.steps
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cells.len() >= self.cell_count()
+    }
+
+    /// Checks the cut with the corner at the center of the cell at `index`, see
+    /// [`Sweep::cells`]
+    pub fn check(&self, index: usize) -> Option<Violations> {
+        let (min_x, min_y) = (self.bounds.min_x().0, self.bounds.min_y().0);
+        let dx = (self.bounds.max_x().0 - min_x) / self.steps as f64;
+        let dy = (self.bounds.max_y().0 - min_y) / self.steps as f64;
+        let (row, column) = (index / self.steps, index % self.steps);
+        let to = StaticPoint2d::from((
+            min_x + (column as f64 + 0.5) * dx,
+            min_y + (row as f64 + 0.5) * dy,
+        ));
+        let moved = self.scene.apply(&Command::MoveCorner {
+            corner: self.corner,
+            to,
+        })?;
+        Some(check_cut(&moved.triangle, &moved.cutter))
+    }
+
+    /// Whether the sweep still describes `scene`, which is the case as long as only the
+    /// swept corner moved
+    pub fn applies_to(&self, scene: &Scene) -> bool {
+        let fixed = |s: &Scene| {
+            (0..3)
+                .filter(|idx| *idx != self.corner)
+                .map(|idx| s.triangle.get_point(idx).copied())
+                .collect::<Vec<_>>()
+        };
+        scene.cutter == self.scene.cutter && fixed(scene) == fixed(&self.scene)
+    }
+
+    pub fn counts(&self) -> SweepCounts {
+        let mut counts = SweepCounts::default();
+        for violations in self.cells.iter().flatten() {
+            counts.checked += 1;
+            counts.panicked += usize::from(violations.panicked);
+            counts.area_mismatch += usize::from(violations.area_mismatch);
+            counts.degenerate_output += usize::from(violations.degenerate_output);
+        }
+        counts
+    }
+
+    /// Heat map of the violation severity, meant to be drawn behind the scene, cells
+    /// which are not checked yet stay empty
+    pub fn figure(&self) -> Figure {
+        let (min_x, min_y) = (self.bounds.min_x().0, self.bounds.min_y().0);
+        let cell_size = (
+            (self.bounds.max_x().0 - min_x) / self.steps as f64,
+            (self.bounds.max_y().0 - min_y) / self.steps as f64,
+        );
+        Figure::heat_map(
+            CssStyle::Color(CssColor::Red),
+            StaticPoint2d::from((min_x, min_y)),
+            cell_size,
+            self.steps,
+            (0..self.cell_count())
+                .map(|index| {
+                    self.cells
+                        .get(index)
+                        .copied()
+                        .flatten()
+                        .map_or(0.0, |violations| violations.severity())
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use triangles::prelude::BoundingBoxValues;
+
+    use crate::scene::sweep::Sweep;
+    use crate::scene::{Command, Scene};
+
+    #[test]
+    fn test_sweep() {
+        let scene = Scene::default();
+        let bounds = Sweep::region(&scene).unwrap();
+        let sweep = Sweep::run(&scene, 2, bounds.clone(), 6);
+        assert_eq!(sweep.cells.len(), 36);
+        // the lowest row lies below the base of the triangle, which would flip it
+        assert!(sweep.cells[..6].iter().all(Option::is_none));
+        assert!(sweep.cells[30..].iter().all(Option::is_some));
+        assert_eq!(
+            sweep.counts().checked,
+            sweep.cells.iter().filter(|cell| cell.is_some()).count()
+        );
+        assert_eq!(sweep.figure().geometry().heat_map_cells().len(), 36);
+
+        // checking cell by cell gives the same cells
+        let mut partial = Sweep::new(&scene, 2, bounds, 6);
+        assert_eq!(partial.figure().geometry().heat_map_cells().len(), 36);
+        while !partial.is_complete() {
+            partial.cells.push(partial.check(partial.cells.len()));
+        }
+        assert_eq!(partial, sweep);
+
+        let moved = |corner| {
+            scene
+                .apply(&Command::MoveCorner {
+                    corner,
+                    to: (0.0, 60.0).into(),
+                })
+                .unwrap()
+        };
+        assert!(sweep.applies_to(&moved(2)));
+        assert!(!sweep.applies_to(&moved(1)));
+
+        let empty = BoundingBoxValues::new(0.0.into(), 0.0.into(), 0.0.into(), 0.0.into());
+        assert_eq!(Sweep::run(&scene, 0, empty, 0).cells.len(), 1);
+    }
+}